@@ -0,0 +1,141 @@
+use crate::deserialize::{collect_results, Error, JsonApiDeserialize, Rebind};
+use crate::field::Field;
+use crate::included::IncludedMap;
+use serde_json::Value;
+use std::ops::Deref;
+
+fn resource_type(value: &Value) -> Result<&str, Error> {
+    value
+        .as_object()
+        .ok_or(Error::InvalidType("Expected an object"))?
+        .get("type")
+        .ok_or(Error::MissingResourceType)?
+        .as_str()
+        .ok_or(Error::InvalidType("Expected a string"))
+}
+
+macro_rules! any_of {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident),+ }) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub enum $name<$($variant),+> {
+            $($variant($variant)),+
+        }
+
+        impl<'gc, $($variant),+> JsonApiDeserialize<'gc> for $name<$($variant),+>
+        where
+            $($variant: JsonApiDeserialize<'gc>),+
+        {
+            type ErasedLifetime = $name<$($variant::ErasedLifetime),+>;
+
+            fn from_value(
+                value: &Value,
+                included_map: &mut IncludedMap<'_, 'gc>,
+            ) -> Result<Self, Error> {
+                let kind = resource_type(value)?;
+
+                $(
+                    if $variant::accepts_resource_type(kind) {
+                        return $variant::from_value(value, included_map).map(Self::$variant);
+                    }
+                )+
+
+                Err(Error::ResourceTypeMismatch {
+                    expected: std::any::type_name::<Self>().to_string(),
+                    found: kind.to_string(),
+                })
+            }
+
+            fn accepts_resource_type(kind: &str) -> bool {
+                $($variant::accepts_resource_type(kind))||+
+            }
+        }
+
+        unsafe impl<$($variant: Rebind),+> Rebind for $name<$($variant),+> {
+            type Rebound<'gc> = $name<$($variant::Rebound<'gc>),+>;
+        }
+    };
+}
+
+any_of!(
+    /// A resource of one of two types, picked by the `type` member.
+    AnyOf2 { A, B }
+);
+any_of!(
+    /// A resource of one of three types, picked by the `type` member.
+    AnyOf3 { A, B, C }
+);
+any_of!(
+    /// A resource of one of four types, picked by the `type` member.
+    AnyOf4 { A, B, C, D }
+);
+any_of!(
+    /// A resource of one of five types, picked by the `type` member.
+    AnyOf5 { A, B, C, D, E }
+);
+any_of!(
+    /// A resource of one of six types, picked by the `type` member.
+    AnyOf6 { A, B, C, D, E, F }
+);
+
+/// A collection of resources which skips elements whose type is not accepted by `T`, instead of
+/// failing the entire document.
+#[derive(Debug)]
+pub struct IgnoreUnknown<T>(pub Vec<T>);
+
+impl<T> IgnoreUnknown<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for IgnoreUnknown<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IgnoreUnknown<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'gc, T> JsonApiDeserialize<'gc> for IgnoreUnknown<T>
+where
+    T: JsonApiDeserialize<'gc>,
+{
+    type ErasedLifetime = IgnoreUnknown<T::ErasedLifetime>;
+
+    fn from_value(value: &Value, included_map: &mut IncludedMap<'_, 'gc>) -> Result<Self, Error> {
+        let collect_errors = included_map.collects_errors();
+        let resources = value
+            .as_array()
+            .ok_or(Error::InvalidType("Expected an array"))?
+            .iter()
+            .filter_map(|value| match resource_type(value) {
+                Ok(kind) if !T::accepts_resource_type(kind) => None,
+                Ok(_) => Some(T::from_value(value, included_map)),
+                Err(error) => Some(Err(error)),
+            });
+
+        collect_results(resources, collect_errors).map(Self)
+    }
+
+    fn accepts_resource_type(kind: &str) -> bool {
+        T::accepts_resource_type(kind)
+    }
+
+    fn fields() -> &'static [Field] {
+        T::fields()
+    }
+}
+
+unsafe impl<T: Rebind> Rebind for IgnoreUnknown<T> {
+    type Rebound<'gc> = IgnoreUnknown<T::Rebound<'gc>>;
+}
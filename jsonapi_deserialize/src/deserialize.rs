@@ -1,7 +1,19 @@
-use std::any::TypeId;
-use crate::document::{Document, Holder, RawDocument};
-use crate::included::IncludedMap;
+use crate::document::{Document, ErrorDocument, RawDocument, SharedDocument};
+use crate::extension::{extension_members, validate_extension_members};
+use crate::field::Field;
+use crate::holder::{alloc_uninit, alloc_value, private::Sealed, Arena, Holder};
+use crate::included::{resource_identity, DocumentIndex, IncludedMap};
+use crate::options::DeserializeOptions;
+use crate::path::{ErrorPath, PathSegment};
+use crate::policy::{apply_policy, Warning};
+use crate::span::Span;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, PoisonError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -27,14 +39,193 @@ pub enum Error {
     #[error("Missing field")]
     MissingField(&'static str),
 
+    #[error("Unknown field")]
+    UnknownField(String),
+
     #[error("Missing resource")]
     MissingResource { kind: String, id: String },
 
     #[error("Resource type mismatch")]
     ResourceTypeMismatch { expected: String, found: String },
 
+    #[error(
+        "Cyclic reference to a resource which is still being deserialized, use a lazy relationship \
+         or a weak pointer"
+    )]
+    CyclicReference { kind: String, id: String },
+
+    #[error("Document uses an unsupported extension")]
+    UnsupportedExtension(String),
+
+    #[error("Resource is included more than once")]
+    DuplicateResource { kind: String, id: String },
+
+    #[error("Local identifier is used for more than one resource")]
+    InconsistentLid { kind: String, lid: String },
+
+    #[error("Invalid extension member name")]
+    InvalidExtensionMember(String),
+
+    #[error("Invalid document meta")]
+    InvalidMeta(#[source] serde_json::Error),
+
     #[error(transparent)]
     SerdeError(#[from] serde_json::Error),
+
+    #[error("{} errors:\n{}", .0.len(), render_errors(.0))]
+    Multiple(Vec<Error>),
+
+    /// An error which occurred within a resource, along with where it occurred. Match on
+    /// [`Error::kind`] rather than on the variants directly to handle both forms.
+    #[error("{}{error}", .path.prefix())]
+    AtPath {
+        path: ErrorPath,
+        span: Option<Span>,
+        error: Box<Error>,
+    },
+}
+
+fn render_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("- {}", error))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Error {
+    /// Aggregates errors, flattening nested aggregates and dropping duplicates, e.g. from a
+    /// resource which is referenced more than once.
+    #[doc(hidden)]
+    pub fn multiple(errors: Vec<Error>) -> Self {
+        let mut flattened: Vec<Error> = Vec::new();
+        let mut seen = HashSet::new();
+
+        for error in errors.into_iter().flat_map(|error| match error {
+            Self::Multiple(errors) => errors,
+            error => vec![error],
+        }) {
+            // The same resource reached through different relationships has a different path
+            // prefix, so only the path from the innermost resource on is compared.
+            let location = error.path().map(|path| {
+                let start = path
+                    .segments
+                    .iter()
+                    .rposition(|segment| matches!(segment, PathSegment::Resource { .. }))
+                    .unwrap_or(0);
+                path.segments[start..].to_vec()
+            });
+
+            if seen.insert((location, error.kind().to_string())) {
+                flattened.push(error);
+            }
+        }
+
+        match flattened.len() {
+            1 => flattened.remove(0),
+            _ => Self::Multiple(flattened),
+        }
+    }
+
+    /// The individual errors, which is more than one for [`Error::Multiple`].
+    pub fn errors(&self) -> &[Error] {
+        match self {
+            Self::Multiple(errors) => errors,
+            error => std::slice::from_ref(error),
+        }
+    }
+
+    /// The error without the path it occurred at, which is never [`Error::AtPath`].
+    ///
+    /// This is what to match on, as errors within a resource are wrapped in [`Error::AtPath`].
+    pub fn kind(&self) -> &Error {
+        match self {
+            Self::AtPath { error, .. } => error,
+            error => error,
+        }
+    }
+
+    /// Where in the document the error occurred, if known.
+    pub fn path(&self) -> Option<&ErrorPath> {
+        match self {
+            Self::AtPath { path, .. } if !path.segments.is_empty() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Where in the JSON input the error occurred, if known.
+    ///
+    /// Only errors returned while deserializing a document have a span, not those returned when
+    /// resolving resources afterwards. Errors without a path, e.g. syntax errors, have no span
+    /// either. A [`Error::SerdeError`] reports its own line and column instead.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::AtPath { span, .. } => *span,
+            _ => None,
+        }
+    }
+
+    /// Prepends segments to the path of the error.
+    pub(crate) fn at(self, segments: impl IntoIterator<Item = PathSegment>) -> Self {
+        let (mut path, span, error) = match self {
+            Self::AtPath { path, span, error } => (path, span, error),
+            Self::Multiple(errors) => {
+                let segments = segments.into_iter().collect::<Vec<_>>();
+
+                return Self::Multiple(
+                    errors
+                        .into_iter()
+                        .map(|error| error.at(segments.iter().cloned()))
+                        .collect(),
+                );
+            }
+            error => (ErrorPath::default(), None, Box::new(error)),
+        };
+        path.segments.splice(0..0, segments);
+
+        Self::AtPath { path, span, error }
+    }
+
+    /// Locates the error in the JSON input it occurred in.
+    pub(crate) fn with_span(self, json: &str) -> Self {
+        if let Self::Multiple(errors) = self {
+            return Self::Multiple(
+                errors
+                    .into_iter()
+                    .map(|error| error.with_span(json))
+                    .collect(),
+            );
+        }
+
+        // Errors without a path are returned as they are, so that they can still be matched on
+        // directly. Syntax errors among them report their position themselves.
+        let Some(path) = self.path() else {
+            return self;
+        };
+
+        let span = match self.kind() {
+            Error::SerdeError(error) if error.line() > 0 => {
+                Span::from_line_column(json, error.line(), error.column())
+            }
+            _ => Span::from_path(json, path),
+        };
+
+        if span.is_none() {
+            return self;
+        }
+
+        match self.at([]) {
+            Self::AtPath { path, error, .. } => Self::AtPath { path, span, error },
+            _ => unreachable!(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn at_members(self, members: &[&str]) -> Self {
+        self.at(members
+            .iter()
+            .map(|member| PathSegment::Member(member.to_string())))
+    }
 }
 
 pub trait JsonApiDrop {}
@@ -45,10 +236,131 @@ pub trait JsonApiDeserialize<'gc>: Sized + JsonApiDrop {
 
     fn from_value(value: &Value, included_map: &mut IncludedMap<'_, 'gc>) -> Result<Self, Error>;
 
-    fn stub() -> Self;
+    /// Whether a resource object of the given `type` can be deserialized into `Self`.
+    ///
+    /// Accepts any type by default, leaving it to [`Self::from_value`] to reject a mismatch. The
+    /// derive macro checks the type against the resource type of the struct or its variants.
+    fn accepts_resource_type(_kind: &str) -> bool {
+        true
+    }
+
+    /// A placeholder handed out to relationships pointing back at the resource while it is still
+    /// being deserialized, which is replaced by the resource once it has been.
+    ///
+    /// Without one, such a cycle fails with [`Error::CyclicReference`], and has to be broken with
+    /// a [`Lazy`](crate::Lazy) relationship instead. The derive macro returns the
+    /// [`Default`] of types implementing it.
+    fn cycle_stub() -> Option<Self> {
+        None
+    }
+
+    /// Superseded by [`Self::cycle_stub`], which does not require `Default`.
+    #[doc(hidden)]
+    #[deprecated(note = "implement `cycle_stub` instead")]
+    fn stub() -> Self
+    where
+        Self: Default,
+    {
+        Self::default()
+    }
+
+    /// The fields of the resource type and the members they are read from.
+    fn fields() -> &'static [Field] {
+        &[]
+    }
+}
+
+/// Picks the [`Default`] of derived types implementing it as their
+/// [`JsonApiDeserialize::cycle_stub`], and no stub for any other type.
+#[doc(hidden)]
+pub struct CycleStub<T>(pub PhantomData<T>);
+
+impl<T> Clone for CycleStub<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CycleStub<T> {}
+
+#[doc(hidden)]
+pub trait DefaultCycleStub<T> {
+    fn cycle_stub(self) -> Option<T>;
+}
+
+// Implemented for a reference, so that method resolution prefers it over `NoCycleStub`.
+impl<T: Default> DefaultCycleStub<T> for &CycleStub<T> {
+    fn cycle_stub(self) -> Option<T> {
+        Some(T::default())
+    }
+}
+
+#[doc(hidden)]
+pub trait NoCycleStub<T> {
+    fn cycle_stub(self) -> Option<T>;
+}
+
+impl<T> NoCycleStub<T> for CycleStub<T> {
+    fn cycle_stub(self) -> Option<T> {
+        None
+    }
+}
+
+/// Collects results, either failing on the first error or collecting all of them.
+#[doc(hidden)]
+pub fn collect_results<T, C>(
+    results: impl Iterator<Item = Result<T, Error>>,
+    collect_errors: bool,
+) -> Result<C, Error>
+where
+    C: FromIterator<T>,
+{
+    if !collect_errors {
+        return results.collect();
+    }
+
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::multiple(errors));
+    }
+
+    Ok(values.into_iter().collect())
+}
+
+/// Recovers a type borrowing from an arena from its [`JsonApiDeserialize::ErasedLifetime`], the
+/// inverse of which it is.
+///
+/// This is what allows an [`OwnedDocument`](crate::OwnedDocument) to hand out its resources bound
+/// to its own borrow instead of `'static`. It is implemented by the derive macro.
+///
+/// # Safety
+///
+/// `Rebound<'gc>` must be the implementing type with all of its lifetimes replaced by `'gc`, and it
+/// must be covariant in `'gc`, i.e. a `Rebound<'long>` must coerce to a `Rebound<'short>`. A type
+/// holding a `Cell<&'gc T>`, for example, must not implement this trait, as a resource could be
+/// replaced through the shortened lifetime with one which does not live as long as the arena.
+pub unsafe trait Rebind: 'static {
+    type Rebound<'gc>: JsonApiDeserialize<'gc, ErasedLifetime = Self>;
 }
 
 impl<T> JsonApiDrop for T {}
+
+unsafe impl<T: Rebind> Rebind for Option<T> {
+    type Rebound<'gc> = Option<T::Rebound<'gc>>;
+}
+
+unsafe impl<T: Rebind> Rebind for Vec<T> {
+    type Rebound<'gc> = Vec<T::Rebound<'gc>>;
+}
 impl<'gc, T> JsonApiDeserialize<'gc> for Option<T>
 where
     T: JsonApiDeserialize<'gc>,
@@ -62,8 +374,12 @@ where
         T::from_value(value, included_map).map(Some)
     }
 
-    fn stub() -> Self {
-        Some(T::stub())
+    fn accepts_resource_type(kind: &str) -> bool {
+        T::accepts_resource_type(kind)
+    }
+
+    fn fields() -> &'static [Field] {
+        T::fields()
     }
 }
 
@@ -73,48 +389,326 @@ where
 {
     type ErasedLifetime = Vec<T::ErasedLifetime>;
     fn from_value(value: &Value, included_map: &mut IncludedMap<'_, 'gc>) -> Result<Self, Error> {
-        value
+        let collect_errors = included_map.collects_errors();
+        let resources = value
             .as_array()
             .ok_or(Error::InvalidType("Expected an array"))?
             .iter()
-            .map(|value| T::from_value(value, included_map))
-            .collect()
+            .map(|value| T::from_value(value, included_map));
+
+        collect_results(resources, collect_errors)
+    }
+
+    fn accepts_resource_type(kind: &str) -> bool {
+        T::accepts_resource_type(kind)
     }
 
-    fn stub() -> Self {
-        Vec::new()
+    fn fields() -> &'static [Field] {
+        T::fields()
     }
 }
 
-pub fn deserialize_document<'a, 'gc: 'a, T: JsonApiDeserialize<'gc>>(
-    json: &'a str,
-    bump: &'gc Holder,
-) -> Result<Document<'gc, T>, crate::error::Error> {
-    let raw_document: RawDocument = serde_json::from_str(json).map_err(Error::SerdeError)?;
-    let default_included = Vec::new();
-    let included = raw_document.included.as_ref().unwrap_or(&default_included);
+unsafe impl<T: Rebind> Rebind for &'static T {
+    type Rebound<'gc> = &'gc T::Rebound<'gc>;
+}
+
+/// Resources referenced through the identity map, so that every occurrence of the same resource
+/// in the document, including primary data, is the same instance.
+impl<'gc, T> JsonApiDeserialize<'gc> for &'gc T
+where
+    T: JsonApiDeserialize<'gc> + 'gc,
+{
+    type ErasedLifetime = &'static T::ErasedLifetime;
+
+    fn from_value(value: &Value, included_map: &mut IncludedMap<'_, 'gc>) -> Result<Self, Error> {
+        let (kind, identity) = resource_identity(value)?;
+        included_map.get_identified::<T>(kind, identity)
+    }
 
-    let mut included_map = IncludedMap::from_includes(included, &bump);
-    //     match raw_document.included {
-    //     Some(ref resources) => IncludedMap::from_includes(resources, &mutation),
-    //     None => IncludedMap::empty(&mutation),
-    // };
+    fn accepts_resource_type(kind: &str) -> bool {
+        T::accepts_resource_type(kind)
+    }
 
-    if let Some(errors) = raw_document.errors {
-        return Err(crate::error::Error::DocumentError(errors));
+    fn fields() -> &'static [Field] {
+        T::fields()
     }
+}
 
-    let data = bump.bump.alloc(T::from_value(
-        &raw_document.data.ok_or(Error::IncompleteDocument)?,
-        &mut included_map,
-    )?);
-    bump.to_free.borrow_mut().push(data as *mut T as *mut T::ErasedLifetime as *mut dyn JsonApiDrop);
+macro_rules! shared_deserialize {
+    ($pointer:ident) => {
+        impl<'gc, T> JsonApiDeserialize<'gc> for $pointer<T>
+        where
+            T: JsonApiDeserialize<'gc> + 'static,
+        {
+            type ErasedLifetime = $pointer<T>;
 
-    drop(included_map);
+            fn from_value(
+                value: &Value,
+                included_map: &mut IncludedMap<'_, 'gc>,
+            ) -> Result<Self, Error> {
+                let (kind, identity) = resource_identity(value)?;
+                included_map.get_shared::<T, Self>(kind, identity)
+            }
+
+            fn accepts_resource_type(kind: &str) -> bool {
+                T::accepts_resource_type(kind)
+            }
+
+            fn fields() -> &'static [Field] {
+                T::fields()
+            }
+        }
+    };
+}
+
+shared_deserialize!(Arc);
+shared_deserialize!(Rc);
+
+/// Deserializes the top-level `meta` member of a document.
+fn deserialize_meta<M: DeserializeOwned>(meta: Option<Value>) -> Result<Option<M>, Error> {
+    meta.map(serde_json::from_value)
+        .transpose()
+        .map_err(Error::InvalidMeta)
+}
+
+fn error_document(raw_document: RawDocument) -> Result<ErrorDocument, Error> {
+    Ok(ErrorDocument {
+        errors: raw_document.errors.ok_or(Error::IncompleteDocument)?,
+        meta: deserialize_meta(raw_document.meta)?,
+        links: raw_document.links,
+        jsonapi: raw_document.jsonapi,
+    })
+}
+
+/// Deserializes a document which is known to contain errors, e.g. the body of a 4xx or 5xx
+/// response.
+pub fn deserialize_error_document(json: &str) -> Result<ErrorDocument, Error> {
+    serde_json::from_str(json)
+        .map_err(Error::SerdeError)
+        .and_then(error_document)
+        .map_err(|error| error.with_span(json))
+}
+
+/// Parses a document, failing if it contains errors instead of data, and returns the warnings
+/// for the deviations from the specification it tolerated.
+fn parse_document(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<(RawDocument, Vec<Warning>), crate::error::Error> {
+    let mut raw_document: RawDocument = serde_json::from_str(json).map_err(Error::SerdeError)?;
+    let warnings = apply_policy(&mut raw_document, &options.policy)?;
+
+    if raw_document.errors.is_some() {
+        return Err(crate::error::Error::DocumentError(Box::new(
+            error_document(raw_document)?,
+        )));
+    }
+
+    if let (Some(supported), Some(jsonapi)) = (&options.supported_extensions, &raw_document.jsonapi)
+    {
+        let unsupported = jsonapi.ext.iter().find(|extension| {
+            !supported
+                .iter()
+                .any(|supported| *extension == supported.as_str())
+        });
+
+        if let Some(extension) = unsupported {
+            return Err(Error::UnsupportedExtension(extension.to_string()).into());
+        }
+    }
+
+    validate_extension_members(raw_document.members.keys())?;
+
+    let resources = match &raw_document.data {
+        Some(Value::Array(resources)) => resources.as_slice(),
+        Some(resource) => std::slice::from_ref(resource),
+        None => &[],
+    };
+
+    for resource in resources
+        .iter()
+        .chain(raw_document.included.iter().flatten())
+    {
+        if let Some(resource) = resource.as_object() {
+            validate_extension_members(resource.keys())?;
+        }
+    }
+
+    Ok((raw_document, warnings))
+}
+
+pub fn deserialize_document<'a, 'gc: 'a, T>(
+    json: &'a str,
+    holder: &'gc Holder,
+) -> Result<Document<'gc, T>, crate::error::Error>
+where
+    T: JsonApiDeserialize<'gc>,
+{
+    deserialize_document_in(json, holder, &DeserializeOptions::default())
+}
+
+pub fn deserialize_document_with_options<'a, 'gc: 'a, T>(
+    json: &'a str,
+    holder: &'gc Holder,
+    options: &DeserializeOptions,
+) -> Result<Document<'gc, T>, crate::error::Error>
+where
+    T: JsonApiDeserialize<'gc>,
+{
+    deserialize_document_in(json, holder, options)
+}
+
+/// Deserializes a document whose top-level `meta` is deserialized into `M` instead of a map.
+pub fn deserialize_document_with_meta<'a, 'gc: 'a, T, M>(
+    json: &'a str,
+    holder: &'gc Holder,
+) -> Result<Document<'gc, T, M>, crate::error::Error>
+where
+    T: JsonApiDeserialize<'gc>,
+    M: DeserializeOwned,
+{
+    deserialize_document_in(json, holder, &DeserializeOptions::default())
+}
+
+/// Deserializes a document into any arena, e.g. a [`SyncHolder`](crate::SyncHolder), with its
+/// top-level `meta` deserialized into `M`.
+pub fn deserialize_document_in<'a, 'gc: 'a, T, H, M>(
+    json: &'a str,
+    holder: &'gc H,
+    options: &DeserializeOptions,
+) -> Result<Document<'gc, T, M>, crate::error::Error>
+where
+    T: JsonApiDeserialize<'gc>,
+    H: Arena,
+    M: DeserializeOwned,
+{
+    arena_document(json, holder, options).map_err(|error| error.with_span(json))
+}
+
+fn arena_document<'a, 'gc: 'a, T, H, M>(
+    json: &'a str,
+    holder: &'gc H,
+    options: &DeserializeOptions,
+) -> Result<Document<'gc, T, M>, crate::error::Error>
+where
+    T: JsonApiDeserialize<'gc>,
+    H: Arena,
+    M: DeserializeOwned,
+{
+    let (raw_document, warnings) = parse_document(json, options)?;
+    let meta = deserialize_meta(raw_document.meta)?;
+
+    // The raw resources are kept in the arena, so that the document can resolve references to
+    // them after deserialization.
+    // SAFETY: Values do not borrow anything.
+    let data = unsafe { alloc_value(holder, raw_document.data.ok_or(Error::IncompleteDocument)?) };
+    let included = unsafe { alloc_value(holder, raw_document.included.unwrap_or_default()) };
+    let mut included_map = IncludedMap::from_includes(included, holder)?;
+    included_map.index_primary(data)?;
+    included_map.set_collect_errors(options.collect_errors);
+    included_map.set_report_drift(options.report_drift);
+
+    // SAFETY: The identity map only refers to the raw resources and resources in the arena, and
+    // does not access either of them when dropped.
+    let included_map = unsafe { alloc_value(holder, Mutex::new(included_map)) };
+    let mut guard = included_map.lock().unwrap_or_else(PoisonError::into_inner);
+    guard.set_index(included_map);
+
+    // A single primary resource goes through the identity map, so that relationships pointing
+    // back at it resolve to the very same instance.
+    let data = match resource_identity(data) {
+        Ok((kind, identity)) => guard.get_identified::<T>(kind, identity)?,
+        Err(_) => {
+            let value = T::from_value(data, &mut guard)?;
+            let data = alloc_uninit::<T>(holder);
+
+            unsafe {
+                data.write(value);
+                Sealed::defer_drop(
+                    holder,
+                    data as *mut T::ErasedLifetime as *mut dyn JsonApiDrop,
+                );
+                &*data
+            }
+        }
+    };
+
+    let drift = guard.drift_report();
+    drop(guard);
 
     Ok(Document {
         data,
-        meta: raw_document.meta,
+        meta,
+        links: raw_document.links,
+        jsonapi: raw_document.jsonapi,
+        extensions: extension_members(raw_document.members),
+        drift,
+        warnings,
+        included,
+        included_map: DocumentIndex::new(included_map),
+    })
+}
+
+/// Deserializes a document without an arena, sharing resources through reference counting.
+///
+/// Relationships are resolved into `Arc<T>` or `Rc<T>`, and cycles must be broken by typing the
+/// relationships pointing back as `Weak<T>`. Resolving a cycle through strong pointers only
+/// fails with [`Error::CyclicReference`].
+pub fn deserialize_shared_document<T, M>(
+    json: &str,
+) -> Result<SharedDocument<T, M>, crate::error::Error>
+where
+    T: for<'gc> JsonApiDeserialize<'gc> + 'static,
+    M: DeserializeOwned,
+{
+    deserialize_shared_document_with_options(json, &DeserializeOptions::default())
+}
+
+pub fn deserialize_shared_document_with_options<T, M>(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<SharedDocument<T, M>, crate::error::Error>
+where
+    T: for<'gc> JsonApiDeserialize<'gc> + 'static,
+    M: DeserializeOwned,
+{
+    shared_document(json, options).map_err(|error| error.with_span(json))
+}
+
+fn shared_document<T, M>(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<SharedDocument<T, M>, crate::error::Error>
+where
+    T: for<'gc> JsonApiDeserialize<'gc> + 'static,
+    M: DeserializeOwned,
+{
+    let (raw_document, warnings) = parse_document(json, options)?;
+
+    let meta = deserialize_meta(raw_document.meta)?;
+
+    let holder = Holder::default();
+    let data = raw_document
+        .data
+        .as_ref()
+        .ok_or(Error::IncompleteDocument)?;
+    let included = raw_document.included.as_deref().unwrap_or_default();
+    let mut included_map = IncludedMap::from_includes(included, &holder)?;
+    included_map.index_primary(data)?;
+    included_map.set_collect_errors(options.collect_errors);
+    included_map.set_report_drift(options.report_drift);
+
+    let data = T::from_value(data, &mut included_map)?;
+    let drift = included_map.drift_report();
+    drop(included_map);
+
+    Ok(SharedDocument {
+        data,
+        meta,
         links: raw_document.links,
+        jsonapi: raw_document.jsonapi,
+        extensions: extension_members(raw_document.members),
+        drift,
+        warnings,
     })
 }
@@ -0,0 +1,69 @@
+use crate::deserialize::Error;
+use crate::span::Span;
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use std::fmt::{Debug, Display, Formatter};
+
+/// A deserialization error together with the JSON input it occurred in, rendering the location
+/// of the error as a `miette` diagnostic.
+pub struct SourceDiagnostic {
+    error: Error,
+    source_code: String,
+}
+
+impl SourceDiagnostic {
+    pub fn new(error: Error, json: impl Into<String>) -> Self {
+        Self {
+            error,
+            source_code: json.into(),
+        }
+    }
+
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl Debug for SourceDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceDiagnostic")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for SourceDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for SourceDiagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl Diagnostic for SourceDiagnostic {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source_code)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.error.span().or_else(|| match self.error.kind() {
+            Error::SerdeError(error) => {
+                Span::from_line_column(&self.source_code, error.line(), error.column())
+            }
+            _ => None,
+        })?;
+        let label = LabeledSpan::new(Some(self.error.kind().to_string()), span.offset, span.len);
+
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+impl Error {
+    /// Attaches the JSON input to the error, to render it as a `miette` diagnostic.
+    pub fn into_diagnostic(self, json: impl Into<String>) -> SourceDiagnostic {
+        SourceDiagnostic::new(self, json)
+    }
+}
@@ -1,32 +1,143 @@
-use std::cell::RefCell;
-use crate::deserialize::{JsonApiDeserialize, JsonApiDrop};
-use crate::link::Link;
+use crate::deserialize::{Error, JsonApiDeserialize};
+use crate::drift::DriftReport;
+use crate::included::{resource_identity, DocumentIndex, IncludedMap};
+use crate::link::{deserialize_uris, Link};
+use crate::policy::Warning;
+use http::Uri;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::sync::MutexGuard;
 
-#[derive(Default)]
-pub struct Holder{
-    pub(crate) bump: bumpalo::Bump,
-    pub(crate) to_free: RefCell<Vec<*mut dyn JsonApiDrop>>
+pub struct Document<'a, T: 'a, M = HashMap<String, Value>>
+where
+    T: JsonApiDeserialize<'a>,
+{
+    pub data: &'a T,
+    pub meta: Option<M>,
+    pub links: Option<DocumentLinks>,
+    pub jsonapi: Option<JsonApiObject>,
+    /// Members added by extensions, keyed by their full name, e.g. `version:id`.
+    pub extensions: HashMap<String, Value>,
+    /// The parts of the document which were not used, if enabled through
+    /// [`DeserializeOptions::report_drift`](crate::DeserializeOptions::report_drift).
+    pub drift: Option<DriftReport>,
+    /// The deviations from the specification which were tolerated with a warning, see
+    /// [`DeviationPolicy`](crate::DeviationPolicy).
+    pub warnings: Vec<Warning>,
+    pub(crate) included: &'a [Value],
+    pub(crate) included_map: DocumentIndex<'a>,
 }
 
-impl Drop for Holder {
-    fn drop(&mut self) {
-        for ptr in self.to_free.borrow_mut().drain(..) {
-            unsafe { std::ptr::drop_in_place(ptr) };
-        }
+impl<'a, T, M> Document<'a, T, M>
+where
+    T: JsonApiDeserialize<'a>,
+{
+    /// Resolves a reference to a resource in the document, deserializing it if it has not been
+    /// deserialized into `R` yet.
+    pub fn resolve<R>(&self, reference: &impl ResourceIdentifier) -> Result<&'a R, Error>
+    where
+        R: JsonApiDeserialize<'a> + 'a,
+    {
+        self.lock().get_reference::<R>(reference)
+    }
+
+    /// Returns every included resource whose type `R` accepts, in the order they are included.
+    pub fn included_of<R>(&self) -> impl Iterator<Item = Result<&'a R, Error>> + '_
+    where
+        R: JsonApiDeserialize<'a> + 'a,
+    {
+        self.included.iter().filter_map(|resource| {
+            let (kind, identity) = match resource_identity(resource) {
+                Ok(identity) => identity,
+                Err(error) => return Some(Err(error)),
+            };
+
+            if !R::accepts_resource_type(kind) {
+                return None;
+            }
+
+            Some(self.lock().get_identified::<R>(kind, identity))
+        })
+    }
+
+    /// Returns the raw resource object with the given type and ID, either from the primary data
+    /// or from the included resources.
+    pub fn find(&self, kind: &str, id: &str) -> Option<&'a Value> {
+        self.lock().raw(kind, id)
+    }
+
+    fn lock(&self) -> MutexGuard<'a, IncludedMap<'a, 'a>> {
+        self.included_map.lock()
     }
 }
 
-#[derive(Debug)]
-pub struct Document<'a, T: 'a>
+impl<'a, T, M> Debug for Document<'a, T, M>
 where
-    T: JsonApiDeserialize<'a>,
+    T: JsonApiDeserialize<'a> + Debug,
+    M: Debug,
 {
-    pub data: &'a T,
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Document")
+            .field("data", &self.data)
+            .field("meta", &self.meta)
+            .field("links", &self.links)
+            .field("jsonapi", &self.jsonapi)
+            .field("extensions", &self.extensions)
+            .field("drift", &self.drift)
+            .field("warnings", &self.warnings)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A document whose resources are shared through reference counting instead of an arena, see
+/// [`crate::deserialize_shared_document`].
+#[derive(Debug)]
+pub struct SharedDocument<T, M = HashMap<String, Value>> {
+    pub data: T,
+    pub meta: Option<M>,
+    pub links: Option<DocumentLinks>,
+    pub jsonapi: Option<JsonApiObject>,
+    /// Members added by extensions, keyed by their full name, e.g. `version:id`.
+    pub extensions: HashMap<String, Value>,
+    /// The parts of the document which were not used, if enabled through
+    /// [`DeserializeOptions::report_drift`](crate::DeserializeOptions::report_drift).
+    pub drift: Option<DriftReport>,
+    /// The deviations from the specification which were tolerated with a warning, see
+    /// [`DeviationPolicy`](crate::DeviationPolicy).
+    pub warnings: Vec<Warning>,
+}
+
+/// A document containing errors instead of data.
+///
+/// Dereferences to its errors.
+#[derive(Debug)]
+pub struct ErrorDocument {
+    pub errors: Vec<DocumentError>,
     pub meta: Option<HashMap<String, Value>>,
     pub links: Option<DocumentLinks>,
+    pub jsonapi: Option<JsonApiObject>,
+}
+
+impl Deref for ErrorDocument {
+    type Target = [DocumentError];
+
+    fn deref(&self) -> &Self::Target {
+        &self.errors
+    }
+}
+
+/// The top-level `jsonapi` object, describing the server's implementation.
+#[derive(Debug, Deserialize)]
+pub struct JsonApiObject {
+    pub version: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_uris")]
+    pub ext: Vec<Uri>,
+    #[serde(default, deserialize_with = "deserialize_uris")]
+    pub profile: Vec<Uri>,
+    pub meta: Option<HashMap<String, Value>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -69,6 +180,14 @@ pub struct DocumentLinks {
     pub next: Option<Link>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ResourceLinks {
+    #[serde(rename = "self")]
+    pub this: Option<Link>,
+    #[serde(flatten)]
+    pub other: HashMap<String, Option<Link>>,
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Clone, Default)]
 pub struct Reference {
     pub id: String,
@@ -76,56 +195,148 @@ pub struct Reference {
     pub kind: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct RawDocument {
-    pub data: Option<Value>,
-    pub errors: Option<Vec<DocumentError>>,
-    pub meta: Option<HashMap<String, Value>>,
-    pub links: Option<DocumentLinks>,
-    pub included: Option<Vec<RawResource>>,
+impl Reference {
+    /// A reference to the resource with the given type and `id`.
+    pub fn new(kind: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            kind: kind.into(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct RawResource {
-    pub id: String,
+/// A resource identifier object which may identify its resource by its local identifier (`lid`).
+///
+/// A resource which has not been assigned an `id` yet is identified by its `lid` alone, in which
+/// case `id` is `None`.
+#[derive(Debug, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(try_from = "RawLocalReference")]
+pub struct LocalReference {
+    pub id: Option<String>,
+    pub lid: Option<String>,
     #[serde(rename = "type")]
     pub kind: String,
-    pub attributes: Option<Value>,
-    pub relationships: Option<Value>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct RawSingleRelationship {
-    pub data: Reference,
+impl LocalReference {
+    /// A reference to the resource with the given type and `id`.
+    pub fn new(kind: impl Into<String>, id: impl Into<String>) -> Self {
+        Reference::new(kind, id).into()
+    }
+
+    /// A reference to a resource which has not been assigned an `id` yet.
+    pub fn local(kind: impl Into<String>, lid: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            lid: Some(lid.into()),
+            kind: kind.into(),
+        }
+    }
+
+    /// The `id` of the resource, or its `lid` if it has not been assigned an `id` yet.
+    pub fn identifier(&self) -> &str {
+        self.id
+            .as_deref()
+            .or(self.lid.as_deref())
+            .unwrap_or_default()
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct RawOptionalRelationship {
-    pub data: Option<Reference>,
+impl From<Reference> for LocalReference {
+    fn from(reference: Reference) -> Self {
+        Self {
+            id: Some(reference.id),
+            lid: None,
+            kind: reference.kind,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct RawMultipleRelationship {
-    pub data: Vec<Reference>,
+#[derive(Deserialize)]
+struct RawLocalReference {
+    id: Option<String>,
+    lid: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
 }
 
-impl<'a> From<&'a RawResource> for Value {
-    fn from(resource: &'a RawResource) -> Self {
-        let mut value = serde_json::json!({
-            "type": resource.kind,
-            "id": resource.id,
-        });
+impl TryFrom<RawLocalReference> for LocalReference {
+    type Error = &'static str;
 
-        if let Some(attributes) = &resource.attributes {
-            let attrs_value = serde_json::json!(attributes);
-            value["attributes"] = attrs_value;
+    fn try_from(raw: RawLocalReference) -> Result<Self, Self::Error> {
+        if raw.id.is_none() && raw.lid.is_none() {
+            return Err("resource identifier has neither an id nor a lid");
         }
 
-        if let Some(relationships) = &resource.relationships {
-            let rels_value = serde_json::json!(relationships);
-            value["relationships"] = rels_value;
-        }
+        Ok(Self {
+            id: raw.id,
+            lid: raw.lid,
+            kind: raw.kind,
+        })
+    }
+}
+
+/// A reference which resources can be looked up by, i.e. a [`Reference`] or a
+/// [`LocalReference`].
+pub trait ResourceIdentifier {
+    fn kind(&self) -> &str;
 
-        value
+    fn id(&self) -> Option<&str>;
+
+    fn lid(&self) -> Option<&str>;
+}
+
+impl ResourceIdentifier for Reference {
+    fn kind(&self) -> &str {
+        &self.kind
     }
+
+    fn id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn lid(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl ResourceIdentifier for LocalReference {
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn lid(&self) -> Option<&str> {
+        self.lid.as_deref()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RawDocument {
+    pub data: Option<Value>,
+    pub errors: Option<Vec<DocumentError>>,
+    pub meta: Option<Value>,
+    pub links: Option<DocumentLinks>,
+    pub jsonapi: Option<JsonApiObject>,
+    pub included: Option<Vec<Value>>,
+    #[serde(flatten)]
+    pub members: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RawSingleRelationship<R = Reference> {
+    pub data: R,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RawOptionalRelationship<R = Reference> {
+    pub data: Option<R>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RawMultipleRelationship<R = Reference> {
+    pub data: Vec<R>,
 }
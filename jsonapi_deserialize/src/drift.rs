@@ -0,0 +1,208 @@
+use crate::document::LocalReference;
+use crate::field::{Field, FieldKind};
+use crate::included::Identity;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// The parts of a document which the types it was deserialized into do not use, e.g. to notice
+/// when an API starts sending members that are silently dropped.
+///
+/// Enabled through [`DeserializeOptions::report_drift`](crate::DeserializeOptions::report_drift).
+/// Members are reported once per resource type, no matter how many resources contain them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    /// Included resources which were never reached through a relationship.
+    pub unused_included: Vec<LocalReference>,
+    /// Attribute members which no field is read from.
+    pub unmapped_attributes: Vec<UnmappedMember>,
+    /// Relationship members which no field is read from.
+    pub unmapped_relationships: Vec<UnmappedMember>,
+    /// Relationships which are read, but whose linkage points at included resources that were
+    /// never reached, e.g. because the field only keeps the `Reference`.
+    pub ignored_relationships: Vec<UnmappedMember>,
+}
+
+/// A member of the resources of a type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnmappedMember {
+    /// The `type` of the resources, e.g. `article`.
+    pub kind: String,
+    /// The name of the member, e.g. `subtitle`.
+    pub member: String,
+}
+
+impl DriftReport {
+    /// Whether the types used every part of the document.
+    pub fn is_empty(&self) -> bool {
+        self.unused_included.is_empty()
+            && self.unmapped_attributes.is_empty()
+            && self.unmapped_relationships.is_empty()
+            && self.ignored_relationships.is_empty()
+    }
+}
+
+impl Display for DriftReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for reference in &self.unused_included {
+            writeln!(
+                f,
+                "unused included resource {}:{}",
+                reference.kind,
+                reference.identifier()
+            )?;
+        }
+
+        let members = [
+            ("unmapped attribute", &self.unmapped_attributes),
+            ("unmapped relationship", &self.unmapped_relationships),
+            ("ignored relationship", &self.ignored_relationships),
+        ];
+
+        for (description, members) in members {
+            for member in members {
+                writeln!(f, "{} {}.{}", description, member.kind, member.member)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records which resources are reached and which fields their members are read into.
+#[derive(Default)]
+pub(crate) struct DriftTracker<'a> {
+    reached: HashSet<(&'a str, Identity<'a>)>,
+    visited: HashMap<(&'a str, Identity<'a>), Vec<&'static [Field]>>,
+}
+
+impl<'a> DriftTracker<'a> {
+    pub(crate) fn reach(&mut self, kind: &'a str, identity: Identity<'a>) {
+        self.reached.insert((kind, identity));
+    }
+
+    pub(crate) fn visit(
+        &mut self,
+        kind: &'a str,
+        identity: Identity<'a>,
+        fields: &'static [Field],
+    ) {
+        self.visited
+            .entry((kind, identity))
+            .or_default()
+            .push(fields);
+    }
+
+    /// Compares the recorded resources against the resources of the document, keyed by both
+    /// their `id` and their `lid` like in the identity map.
+    pub(crate) fn report(
+        &self,
+        raw_map: &HashMap<(&'a str, Identity<'a>), (Identity<'a>, &'a Value)>,
+        primary: &HashSet<(&'a str, Identity<'a>)>,
+    ) -> DriftReport {
+        let resources = raw_map
+            .iter()
+            .filter(|((_, identity), (canonical, _))| identity == canonical)
+            .map(|((kind, identity), (_, value))| (*kind, *identity, *value))
+            .collect::<Vec<_>>();
+
+        let unused = resources
+            .iter()
+            .map(|(kind, identity, _)| (*kind, *identity))
+            .filter(|key| !primary.contains(key) && !self.reached.contains(key))
+            .collect::<HashSet<_>>();
+
+        let mut unmapped_attributes = BTreeSet::new();
+        let mut unmapped_relationships = BTreeSet::new();
+        let mut ignored_relationships = BTreeSet::new();
+
+        for (kind, identity, value) in &resources {
+            let Some(fields) = self.visited.get(&(*kind, *identity)) else {
+                continue;
+            };
+            let mapped = |member: &str, field_kind: FieldKind| {
+                fields
+                    .iter()
+                    .flat_map(|fields| fields.iter())
+                    .any(|field| field.kind == field_kind && field.member == member)
+            };
+            let unmapped_member = |member: &str| UnmappedMember {
+                kind: kind.to_string(),
+                member: member.to_string(),
+            };
+
+            for member in members(value, "attributes") {
+                if !mapped(member, FieldKind::Attribute) {
+                    unmapped_attributes.insert(unmapped_member(member));
+                }
+            }
+
+            let relationships = value
+                .get("relationships")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten();
+
+            for (member, relationship) in relationships {
+                if !mapped(member, FieldKind::Relationship) {
+                    unmapped_relationships.insert(unmapped_member(member));
+                } else if linkage(relationship)
+                    .filter_map(|key| raw_map.get(&key).map(|(identity, _)| (key.0, *identity)))
+                    .any(|key| unused.contains(&key))
+                {
+                    ignored_relationships.insert(unmapped_member(member));
+                }
+            }
+        }
+
+        let mut unused_included = resources
+            .iter()
+            .filter(|(kind, identity, _)| unused.contains(&(*kind, *identity)))
+            .map(|(kind, identity, value)| LocalReference {
+                id: match identity {
+                    Identity::Id(id) => Some(id.to_string()),
+                    Identity::Lid(_) => None,
+                },
+                lid: value.get("lid").and_then(Value::as_str).map(str::to_string),
+                kind: kind.to_string(),
+            })
+            .collect::<Vec<_>>();
+        unused_included.sort_by(|a, b| (&a.kind, a.identifier()).cmp(&(&b.kind, b.identifier())));
+
+        DriftReport {
+            unused_included,
+            unmapped_attributes: unmapped_attributes.into_iter().collect(),
+            unmapped_relationships: unmapped_relationships.into_iter().collect(),
+            ignored_relationships: ignored_relationships.into_iter().collect(),
+        }
+    }
+}
+
+/// Returns the names of the members of an object member of a resource, e.g. its `attributes`.
+fn members<'v>(resource: &'v Value, member: &str) -> impl Iterator<Item = &'v str> {
+    resource
+        .get(member)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|object| object.keys().map(String::as_str))
+}
+
+/// Returns the identities of the resources a relationship object links to.
+fn linkage(relationship: &Value) -> impl Iterator<Item = (&str, Identity<'_>)> {
+    let data = match relationship.get("data") {
+        Some(Value::Array(data)) => data.as_slice(),
+        Some(data) => std::slice::from_ref(data),
+        None => &[],
+    };
+
+    data.iter().filter_map(|identifier| {
+        let kind = identifier.get("type")?.as_str()?;
+        let identity = match (identifier.get("id"), identifier.get("lid")) {
+            (Some(Value::String(id)), _) => Identity::Id(id),
+            (_, Some(Value::String(lid))) => Identity::Lid(lid),
+            _ => return None,
+        };
+
+        Some((kind, identity))
+    })
+}
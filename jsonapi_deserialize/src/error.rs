@@ -3,8 +3,17 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Document returned errors")]
-    DocumentError(Vec<crate::document::DocumentError>),
+    DocumentError(Box<crate::document::ErrorDocument>),
 
     #[error(transparent)]
     DeserializeError(#[from] crate::deserialize::Error),
 }
+
+impl Error {
+    pub(crate) fn with_span(self, json: &str) -> Self {
+        match self {
+            Self::DeserializeError(error) => Self::DeserializeError(error.with_span(json)),
+            error => error,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::deserialize::Error;
+use crate::member_name::parse_extension_member;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Validates the names of the extension members among the member names of an object.
+pub(crate) fn validate_extension_members<'a>(
+    names: impl IntoIterator<Item = &'a String>,
+) -> Result<(), Error> {
+    match names
+        .into_iter()
+        .find(|name| name.contains(':') && parse_extension_member(name).is_none())
+    {
+        Some(name) => Err(Error::InvalidExtensionMember(name.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Collects the extension members of an object, which have already been validated.
+pub(crate) fn extension_members(object: HashMap<String, Value>) -> HashMap<String, Value> {
+    object
+        .into_iter()
+        .filter(|(name, _)| name.contains(':'))
+        .collect()
+}
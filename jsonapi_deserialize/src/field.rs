@@ -0,0 +1,94 @@
+use crate::deserialize::JsonApiDeserialize;
+use crate::document::{DocumentError, ErrorSource};
+
+/// Where a field of a resource is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Id,
+    Lid,
+    Attribute,
+    Relationship,
+    Meta,
+    Links,
+    Extension,
+}
+
+/// Describes how a field of a resource type maps to a member of the resource object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the Rust field, e.g. `first_name`.
+    pub name: &'static str,
+    /// The name of the member in the resource object, e.g. `firstName`.
+    pub member: &'static str,
+    pub kind: FieldKind,
+}
+
+/// A JSON pointer into the primary data, resolved to a field of the resource type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath {
+    /// The index of the resource, if the primary data is a collection.
+    pub index: Option<usize>,
+    pub field: Field,
+    /// The segments of the pointer below the member, e.g. for nested attribute objects.
+    pub rest: Vec<String>,
+}
+
+impl FieldPath {
+    /// Resolves a JSON pointer like `/data/attributes/firstName` against the fields of `T`.
+    ///
+    /// Returns `None` if the pointer does not point into the primary data, or at no known field.
+    pub fn resolve<'gc, T: JsonApiDeserialize<'gc>>(pointer: &str) -> Option<Self> {
+        let mut segments = pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .peekable();
+
+        if segments.next()? != "data" {
+            return None;
+        }
+
+        let index = match segments.peek().map(|segment| segment.parse::<usize>()) {
+            Some(Ok(index)) => {
+                segments.next();
+                Some(index)
+            }
+            _ => None,
+        };
+
+        let member = segments.next()?;
+        let (kind, member) = match member.as_str() {
+            "attributes" => (FieldKind::Attribute, segments.next()?),
+            "relationships" => (FieldKind::Relationship, segments.next()?),
+            "id" => (FieldKind::Id, member),
+            "lid" => (FieldKind::Lid, member),
+            "meta" => (FieldKind::Meta, member),
+            "links" => (FieldKind::Links, member),
+            _ => (FieldKind::Extension, member),
+        };
+
+        let field = *T::fields()
+            .iter()
+            .find(|field| field.kind == kind && field.member == member)?;
+
+        Some(Self {
+            index,
+            field,
+            rest: segments.collect(),
+        })
+    }
+}
+
+impl ErrorSource {
+    /// Resolves the `pointer` of the source against the fields of `T`, see [`FieldPath::resolve`].
+    pub fn field_path<'gc, T: JsonApiDeserialize<'gc>>(&self) -> Option<FieldPath> {
+        FieldPath::resolve::<T>(self.pointer.as_deref()?)
+    }
+}
+
+impl DocumentError {
+    /// Resolves the `source.pointer` of the error against the fields of `T`.
+    pub fn field_path<'gc, T: JsonApiDeserialize<'gc>>(&self) -> Option<FieldPath> {
+        self.source.as_ref()?.field_path::<T>()
+    }
+}
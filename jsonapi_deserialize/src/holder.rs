@@ -0,0 +1,114 @@
+use crate::deserialize::JsonApiDrop;
+use std::alloc::Layout;
+use std::cell::RefCell;
+use std::ptr::NonNull;
+use std::sync::{Mutex, PoisonError};
+
+/// Storage which deserialized resources are allocated in, and which drops them once it is
+/// dropped itself.
+pub trait Arena: private::Sealed {}
+
+pub(crate) mod private {
+    use super::*;
+
+    pub trait Sealed {
+        fn alloc_layout(&self, layout: Layout) -> NonNull<u8>;
+
+        /// # Safety
+        ///
+        /// `value` must point at an initialized value allocated in this arena, which is not
+        /// accessed anymore once the arena is dropped.
+        unsafe fn defer_drop(&self, value: *mut dyn JsonApiDrop);
+    }
+}
+
+pub(crate) fn alloc_uninit<T>(arena: &dyn private::Sealed) -> *mut T {
+    arena.alloc_layout(Layout::new::<T>()).cast().as_ptr()
+}
+
+/// Moves `value` into the arena, which drops it once the arena is dropped itself.
+///
+/// # Safety
+///
+/// Dropping `value` must not access anything it borrows, as that may already be gone by then.
+pub(crate) unsafe fn alloc_value<T>(arena: &dyn private::Sealed, value: T) -> &T {
+    let slot = alloc_uninit::<T>(arena);
+    slot.write(value);
+
+    let erased: *mut (dyn JsonApiDrop + '_) = slot;
+    arena.defer_drop(std::mem::transmute::<
+        *mut (dyn JsonApiDrop + '_),
+        *mut (dyn JsonApiDrop + 'static),
+    >(erased));
+
+    &*slot
+}
+
+#[derive(Default)]
+pub struct Holder {
+    bump: bumpalo::Bump,
+    to_free: RefCell<Vec<*mut dyn JsonApiDrop>>,
+}
+
+impl Arena for Holder {}
+
+impl private::Sealed for Holder {
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        self.bump.alloc_layout(layout)
+    }
+
+    unsafe fn defer_drop(&self, value: *mut dyn JsonApiDrop) {
+        self.to_free.borrow_mut().push(value);
+    }
+}
+
+impl Drop for Holder {
+    fn drop(&mut self) {
+        for ptr in self.to_free.borrow_mut().drain(..) {
+            unsafe { std::ptr::drop_in_place(ptr) };
+        }
+    }
+}
+
+/// A [`Holder`] which can safely be allocated in from multiple threads.
+///
+/// On its own it is neither `Send` nor `Sync`, as it has no knowledge of the types allocated in
+/// it. Use it through [`crate::deserialize_sync_document`], which checks that the resources are
+/// thread-safe.
+#[derive(Default)]
+pub struct SyncHolder {
+    bump: Mutex<bumpalo::Bump>,
+    to_free: Mutex<Vec<*mut dyn JsonApiDrop>>,
+}
+
+impl Arena for SyncHolder {}
+
+impl private::Sealed for SyncHolder {
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        // Chunks of the bump allocator never move, so the allocation outlives the lock.
+        self.bump
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .alloc_layout(layout)
+    }
+
+    unsafe fn defer_drop(&self, value: *mut dyn JsonApiDrop) {
+        self.to_free
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(value);
+    }
+}
+
+impl Drop for SyncHolder {
+    fn drop(&mut self) {
+        let to_free = self
+            .to_free
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for ptr in to_free.drain(..) {
+            unsafe { std::ptr::drop_in_place(ptr) };
+        }
+    }
+}
@@ -1,22 +1,110 @@
 use crate::deserialize::{Error, JsonApiDeserialize, JsonApiDrop};
-use crate::document::{Holder, RawResource};
+use crate::document::{LocalReference, ResourceIdentifier};
+use crate::drift::{DriftReport, DriftTracker};
+use crate::field::Field;
+use crate::holder::{alloc_uninit, private::Sealed, Arena};
+use crate::path::{Location, PathSegment};
+use crate::shared::SharedPointer;
 use serde_json::Value;
-use std::any::{type_name, TypeId};
-use std::collections::HashMap;
+use std::any::{type_name, Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::rc::{Rc, Weak as RcWeak};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak as ArcWeak};
 
 pub struct IncludedMap<'a, 'gc: 'a> {
-    holder: &'gc Holder,
-    raw_map: HashMap<(&'a str, &'a str), &'a RawResource>,
-    deserialized_map: HashMap<(&'a str, &'a str, TypeId), (TypeId, &'static str, *mut ())>,
+    arena: &'gc dyn Sealed,
+    /// Maps both the `id` and the `lid` of a resource to its canonical identity.
+    raw_map: HashMap<(&'a str, Identity<'a>), (Identity<'a>, &'a Value)>,
+    primary: HashSet<(&'a str, Identity<'a>)>,
+    deserialized_map: HashMap<(&'a str, Identity<'a>, TypeId), (TypeId, &'static str, *mut ())>,
+    /// Resources which are being deserialized, with their slot and whether it holds a stub.
+    pending: HashMap<(&'a str, Identity<'a>, TypeId), (*mut (), bool)>,
+    shared_map: HashMap<(&'a str, Identity<'a>, TypeId), Box<dyn Any>>,
+    index: Option<DocumentIndex<'gc>>,
+    collect_errors: bool,
+    drift: Option<DriftTracker<'a>>,
+}
+
+/// The identity map of a document, which outlives deserialization to resolve resources later on.
+///
+/// Unlike a `&'gc Mutex<IncludedMap<'gc, 'gc>>`, this is covariant in `'gc`, so that documents
+/// and lazy relationships can be shortened to the borrow of an
+/// [`OwnedDocument`](crate::OwnedDocument).
+#[derive(Clone, Copy)]
+pub(crate) struct DocumentIndex<'gc> {
+    map: NonNull<Mutex<IncludedMap<'static, 'static>>>,
+    _gc: PhantomData<&'gc ()>,
+}
+
+impl<'gc> DocumentIndex<'gc> {
+    pub(crate) fn new(map: &'gc Mutex<IncludedMap<'gc, 'gc>>) -> Self {
+        Self {
+            map: NonNull::from(map).cast(),
+            _gc: PhantomData,
+        }
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'gc, IncludedMap<'gc, 'gc>> {
+        // SAFETY: The map lives in the arena for at least `'gc`. Shortening its lifetimes is
+        // sound, as it only ever stores resources it allocated in that very arena itself.
+        let map = unsafe { self.map.cast::<Mutex<IncludedMap<'gc, 'gc>>>().as_ref() };
+
+        map.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Identifies a resource within a document, either by its `id` or, if it has none, by its
+/// local identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Identity<'a> {
+    Id(&'a str),
+    Lid(&'a str),
+}
+
+impl<'a> Identity<'a> {
+    fn as_str(&self) -> &'a str {
+        match self {
+            Self::Id(id) | Self::Lid(id) => id,
+        }
+    }
+}
+
+enum SharedEntry<T, P: SharedPointer<T>> {
+    Pending(P::Weak),
+    Ready(P),
 }
 
 impl<'doc, 'gc: 'doc> IncludedMap<'doc, 'gc> {
-    pub fn get<T: 'gc>(&mut self, kind: &str, id: &str) -> Result<&'gc T, Error>
+    pub fn get<T>(&mut self, kind: &str, id: &str) -> Result<&'gc T, Error>
     where
-        T: JsonApiDeserialize<'gc>,
+        T: JsonApiDeserialize<'gc> + 'gc,
     {
+        self.get_identified(kind, Identity::Id(id))
+    }
 
-        if let Some((zid, type_name, ptr)) = self.deserialized_map.get(&(kind, id, T::TYPE_ID)).cloned() {
+    /// Returns the resource a reference points at, by its `id` or otherwise by its `lid`.
+    pub fn get_reference<T>(&mut self, reference: &impl ResourceIdentifier) -> Result<&'gc T, Error>
+    where
+        T: JsonApiDeserialize<'gc> + 'gc,
+    {
+        let identity = self.reference_identity(reference)?;
+        self.get_identified(reference.kind(), identity)
+    }
+
+    pub(crate) fn get_identified<T>(
+        &mut self,
+        kind: &str,
+        identity: Identity<'_>,
+    ) -> Result<&'gc T, Error>
+    where
+        T: JsonApiDeserialize<'gc> + 'gc,
+    {
+        let (kind, identity, value) = self.raw_resource(kind, identity)?;
+
+        let key = (kind, identity, T::TYPE_ID);
+        if let Some((zid, type_name, ptr)) = self.deserialized_map.get(&key).cloned() {
             if zid == T::TYPE_ID {
                 // SAFETY: In theory, this could be used to expand the lifetime of T, but we'll be careful™
                 return Ok(unsafe { &*(ptr as *const T) });
@@ -24,53 +112,473 @@ impl<'doc, 'gc: 'doc> IncludedMap<'doc, 'gc> {
                 return Err(Error::ResourceTypeMismatch {
                     expected: std::any::type_name::<T>().to_owned(),
                     found: type_name.to_owned(),
+                });
+            }
+        }
+
+        // A relationship pointing back at a resource which is still being deserialized is handed
+        // a stub in its place, which is replaced once the resource has been deserialized.
+        if let Some((slot, stubbed)) = self.pending.get_mut(&key) {
+            let slot = *slot as *mut T;
+
+            if !*stubbed {
+                let stub = T::cycle_stub().ok_or_else(|| Error::CyclicReference {
+                    kind: kind.to_string(),
+                    id: identity.as_str().to_string(),
+                })?;
+
+                // SAFETY: The slot was allocated for a `T` below and has not been initialized yet.
+                unsafe { slot.write(stub) };
+                *stubbed = true;
+            }
+
+            // SAFETY: The slot holds the stub, which is only ever replaced by the resource.
+            return Ok(unsafe { &*slot });
+        }
+
+        let slot = alloc_uninit::<T>(self.arena);
+        self.pending.insert(key, (slot as *mut (), false));
+
+        let result = T::from_value(value, self);
+        let stubbed = self
+            .pending
+            .remove(&key)
+            .is_some_and(|(_, stubbed)| stubbed);
+
+        let resource = match result {
+            Ok(resource) => resource,
+            Err(error) => {
+                if stubbed {
+                    // SAFETY: The slot holds the stub, which resources deserialized along the way
+                    // may still point at, so it is kept alive alongside them.
+                    unsafe {
+                        self.arena
+                            .defer_drop(slot as *mut T::ErasedLifetime as *mut dyn JsonApiDrop);
+                    }
+                }
+
+                return Err(error);
+            }
+        };
+
+        // SAFETY: The slot was allocated for a `T` above. If relationships pointing back at the
+        // resource were handed the stub, it is replaced in place, so that they observe the
+        // resource once deserialization has finished. This writes behind references which have
+        // already been handed out, which is what cycles have always relied on, but which Miri's
+        // aliasing models reject; a `Lazy` relationship avoids the stub altogether.
+        let resource = unsafe {
+            if stubbed {
+                *slot = resource;
+            } else {
+                slot.write(resource);
+            }
+
+            self.arena
+                .defer_drop(slot as *mut T::ErasedLifetime as *mut dyn JsonApiDrop);
+            &*slot
+        };
+
+        self.deserialized_map
+            .insert(key, (T::TYPE_ID, type_name::<T>(), slot as *mut ()));
+
+        Ok(resource)
+    }
+
+    /// Returns a reference counted pointer to the resource, e.g. an `Arc<T>` or an `Rc<T>`.
+    ///
+    /// Resources referring back to a resource which is still being deserialized must do so
+    /// through a weak pointer, see [`Self::get_shared_weak`].
+    pub(crate) fn get_shared<T, P>(
+        &mut self,
+        kind: &str,
+        identity: Identity<'_>,
+    ) -> Result<P, Error>
+    where
+        T: JsonApiDeserialize<'gc> + 'static,
+        P: SharedPointer<T>,
+    {
+        let (kind, identity, value) = self.raw_resource(kind, identity)?;
+
+        match self.shared_map.get(&(kind, identity, TypeId::of::<P>())) {
+            Some(entry) => match entry.downcast_ref::<SharedEntry<T, P>>() {
+                Some(SharedEntry::Ready(pointer)) => Ok(pointer.clone()),
+                _ => Err(Error::CyclicReference {
+                    kind: kind.to_string(),
+                    id: identity.as_str().to_string(),
+                }),
+            },
+            None => self.deserialize_shared(kind, identity, value),
+        }
+    }
+
+    /// Returns a weak pointer to the resource, which may still be in the process of being
+    /// deserialized.
+    ///
+    /// The resource is dropped once deserialization has finished, unless something else holds
+    /// a strong pointer to it.
+    pub(crate) fn get_shared_weak<T, P>(
+        &mut self,
+        kind: &str,
+        identity: Identity<'_>,
+    ) -> Result<P::Weak, Error>
+    where
+        T: JsonApiDeserialize<'gc> + 'static,
+        P: SharedPointer<T>,
+    {
+        let (kind, identity, value) = self.raw_resource(kind, identity)?;
+
+        if let Some(entry) = self.shared_map.get(&(kind, identity, TypeId::of::<P>())) {
+            return match entry.downcast_ref::<SharedEntry<T, P>>() {
+                Some(SharedEntry::Ready(pointer)) => Ok(P::downgrade(pointer)),
+                Some(SharedEntry::Pending(weak)) => Ok(weak.clone()),
+                None => unreachable!("shared entries are keyed by their pointer type"),
+            };
+        }
+
+        self.deserialize_shared::<T, P>(kind, identity, value)
+            .map(|pointer| P::downgrade(&pointer))
+    }
+
+    fn deserialize_shared<T, P>(
+        &mut self,
+        kind: &'doc str,
+        identity: Identity<'doc>,
+        value: &'doc Value,
+    ) -> Result<P, Error>
+    where
+        T: JsonApiDeserialize<'gc> + 'static,
+        P: SharedPointer<T>,
+    {
+        let key = (kind, identity, TypeId::of::<P>());
+        let result = P::try_new_cyclic(|weak| {
+            self.shared_map
+                .insert(key, Box::new(SharedEntry::<T, P>::Pending(weak.clone())));
+
+            T::from_value(value, self)
+        });
+
+        match result {
+            Ok(pointer) => {
+                self.shared_map
+                    .insert(key, Box::new(SharedEntry::<T, P>::Ready(pointer.clone())));
+
+                Ok(pointer)
+            }
+            Err(error) => {
+                // Weak pointers handed out so far will simply never be upgradable.
+                self.shared_map.remove(&key);
+
+                Err(error)
+            }
+        }
+    }
+
+    /// Looks up a resource without deserializing it, recording it as reached for the drift report.
+    pub(crate) fn reach(&mut self, kind: &str, identity: Identity<'_>) -> Result<(), Error> {
+        self.raw_resource(kind, identity).map(|_| ())
+    }
+
+    /// Looks up a resource, returning its canonical identity.
+    fn raw_resource(
+        &mut self,
+        kind: &str,
+        identity: Identity<'_>,
+    ) -> Result<(&'doc str, Identity<'doc>, &'doc Value), Error> {
+        let (identity, value) =
+            *self
+                .raw_map
+                .get(&(kind, identity))
+                .ok_or_else(|| Error::MissingResource {
+                    kind: kind.to_string(),
+                    id: identity.as_str().to_string(),
+                })?;
+        let (kind, _) = resource_identity(value)?;
+
+        if let Some(drift) = &mut self.drift {
+            drift.reach(kind, identity);
+        }
+
+        Ok((kind, identity, value))
+    }
+
+    /// Returns how a reference identifies its resource, checking that its `id` and `lid` agree.
+    pub(crate) fn reference_identity<'r>(
+        &self,
+        reference: &'r (impl ResourceIdentifier + ?Sized),
+    ) -> Result<Identity<'r>, Error> {
+        let (id, lid) = match (reference.id(), reference.lid()) {
+            (Some(id), Some(lid)) => (id, lid),
+            (Some(id), None) => return Ok(Identity::Id(id)),
+            (None, Some(lid)) => return Ok(Identity::Lid(lid)),
+            (None, None) => {
+                return Err(Error::MissingResource {
+                    kind: reference.kind().to_string(),
+                    id: String::new(),
                 })
             }
+        };
+
+        match self.raw_map.get(&(reference.kind(), Identity::Lid(lid))) {
+            Some((identity, _)) if *identity != Identity::Id(id) => Err(Error::InconsistentLid {
+                kind: reference.kind().to_string(),
+                lid: lid.to_string(),
+            }),
+            _ => Ok(Identity::Id(id)),
         }
+    }
 
-        let (kind, id, value) = {
-            let raw_resource =
-                self.raw_map
-                    .get(&(kind, id))
-                    .ok_or_else(|| Error::MissingResource {
-                        kind: kind.to_string(),
-                        id: id.to_string(),
-                    })?;
-            let kind = raw_resource.kind.as_str();
-            let id = raw_resource.id.as_str();
-            let value: Value = (*raw_resource).into();
+    pub(crate) fn raw(&self, kind: &str, id: &str) -> Option<&'doc Value> {
+        self.raw_map
+            .get(&(kind, Identity::Id(id)))
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns the identity map of the document, which outlives deserialization, if there is one.
+    pub(crate) fn index(&self) -> Option<DocumentIndex<'gc>> {
+        self.index
+    }
+
+    /// Whether deserialization continues after an error, to collect all errors in the document.
+    pub fn collects_errors(&self) -> bool {
+        self.collect_errors
+    }
+
+    /// Adds the resource to the path of an error which occurred while deserializing it.
+    #[doc(hidden)]
+    pub fn resource_error(&self, resource: &Value, error: Error) -> Error {
+        let Ok((kind, identity)) = resource_identity(resource) else {
+            return error;
+        };
+
+        let location = if self.raw_map.contains_key(&(kind, identity))
+            && !self.primary.contains(&(kind, identity))
+        {
+            Location::Included
+        } else {
+            Location::Data
+        };
 
-            (kind, id, value)
+        error.at([PathSegment::Resource {
+            location,
+            kind: kind.to_string(),
+            id: identity.as_str().to_string(),
+        }])
+    }
+
+    /// Records the fields a resource was deserialized into, for the drift report.
+    #[doc(hidden)]
+    pub fn visit_resource(&mut self, resource: &Value, fields: &'static [Field]) {
+        let Some(drift) = &mut self.drift else {
+            return;
+        };
+        let Ok((kind, identity)) = resource_identity(resource) else {
+            return;
         };
-        // Put a stub in place, to handle cycles
-        let new_item = self.holder.bump.alloc_with(T::stub);
-        self.holder.to_free.borrow_mut().push(new_item as *mut T as *mut T::ErasedLifetime as *mut dyn JsonApiDrop);
-        self.deserialized_map.insert((kind, id, T::TYPE_ID), (T::TYPE_ID, type_name::<T>(), new_item as *mut T as *mut ()));
-        *new_item = T::from_value(&value, self)?;
-        Ok(new_item as &'gc T)
-    }
-    pub fn empty(holder: &'gc Holder) -> Self{
+
+        if let Some((identity, value)) = self.raw_map.get(&(kind, identity)).copied() {
+            if let Ok((kind, _)) = resource_identity(value) {
+                drift.visit(kind, identity, fields);
+            }
+        }
+    }
+
+    /// Reports the parts of the document which were not used so far, if drift is tracked.
+    pub(crate) fn drift_report(&self) -> Option<DriftReport> {
+        self.drift
+            .as_ref()
+            .map(|drift| drift.report(&self.raw_map, &self.primary))
+    }
+
+    /// Whether the document contains the resource.
+    pub fn contains(&self, kind: &str, id: &str) -> bool {
+        self.raw_map.contains_key(&(kind, Identity::Id(id)))
+    }
+
+    /// Whether the document contains the resource a reference points at.
+    pub fn contains_reference(&self, reference: &impl ResourceIdentifier) -> bool {
+        self.reference_identity(reference)
+            .is_ok_and(|identity| self.contains_identified(reference.kind(), identity))
+    }
+
+    pub(crate) fn contains_identified(&self, kind: &str, identity: Identity<'_>) -> bool {
+        self.raw_map.contains_key(&(kind, identity))
+    }
+
+    pub fn empty(holder: &'gc impl Arena) -> Self {
         Self {
-            holder,
+            arena: holder,
             raw_map: HashMap::new(),
+            primary: HashSet::new(),
             deserialized_map: HashMap::new(),
+            pending: HashMap::new(),
+            shared_map: HashMap::new(),
+            index: None,
+            collect_errors: false,
+            drift: None,
         }
     }
 }
 
+impl<'a, 'gc> IncludedMap<'a, 'gc> {
+    pub(crate) fn set_index(&mut self, index: &'gc Mutex<IncludedMap<'gc, 'gc>>) {
+        self.index = Some(DocumentIndex::new(index));
+    }
 
+    pub(crate) fn set_collect_errors(&mut self, collect_errors: bool) {
+        self.collect_errors = collect_errors;
+    }
 
-impl<'a, 'gc> IncludedMap<'a, 'gc> {
-    pub(crate) fn from_includes(resources: &'a Vec<RawResource>, holder: &'gc Holder) -> Self {
-        let raw_map = resources
-            .iter()
-            .map(|raw| ((raw.kind.as_str(), raw.id.as_str()), raw))
-            .collect();
+    pub(crate) fn set_report_drift(&mut self, report_drift: bool) {
+        self.drift = report_drift.then(DriftTracker::default);
+    }
 
-        Self {
-            holder,
-            raw_map,
+    pub(crate) fn from_includes(
+        resources: &'a [Value],
+        arena: &'gc dyn Sealed,
+    ) -> Result<Self, Error> {
+        let mut included_map = Self {
+            arena,
+            raw_map: HashMap::new(),
+            primary: HashSet::new(),
             deserialized_map: HashMap::new(),
+            pending: HashMap::new(),
+            shared_map: HashMap::new(),
+            index: None,
+            collect_errors: false,
+            drift: None,
+        };
+
+        for resource in resources {
+            included_map.insert_raw(resource)?;
+        }
+
+        Ok(included_map)
+    }
+
+    /// Indexes the primary data, so that relationships pointing at it resolve to the same
+    /// instance as the document's data.
+    pub(crate) fn index_primary(&mut self, data: &'a Value) -> Result<(), Error> {
+        let resources = match data {
+            Value::Array(resources) => resources.as_slice(),
+            _ => std::slice::from_ref(data),
+        };
+
+        // Primary data which cannot be identified can still be deserialized, it just cannot be
+        // referred to.
+        for resource in resources {
+            if let Ok(identity) = resource_identity(resource) {
+                self.insert_raw(resource)?;
+                self.primary.insert(identity);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insert_raw(&mut self, resource: &'a Value) -> Result<(), Error> {
+        let (kind, identity) = resource_identity(resource)?;
+
+        if let Some(lid) = resource_lid(resource)? {
+            // A local identifier must refer to the same resource throughout the document.
+            let key = (kind, Identity::Lid(lid));
+
+            if let Some((existing, _)) = self.raw_map.get(&key) {
+                if *existing != identity {
+                    return Err(Error::InconsistentLid {
+                        kind: kind.to_string(),
+                        lid: lid.to_string(),
+                    });
+                }
+            }
+
+            self.raw_map.insert(key, (identity, resource));
         }
+
+        self.raw_map.insert((kind, identity), (identity, resource));
+
+        Ok(())
     }
 }
+
+/// Extracts the `type` and the identity of a resource object, which is its `id` or, if it has
+/// none, its `lid`.
+pub(crate) fn resource_identity(resource: &Value) -> Result<(&str, Identity<'_>), Error> {
+    let object = resource
+        .as_object()
+        .ok_or(Error::InvalidType("Expected an object"))?;
+    let kind = object
+        .get("type")
+        .ok_or(Error::MissingResourceType)?
+        .as_str()
+        .ok_or(Error::InvalidType("Expected a string"))?;
+
+    let identity = match object.get("id") {
+        Some(id) => Identity::Id(id.as_str().ok_or(Error::InvalidType("Expected a string"))?),
+        None => Identity::Lid(resource_lid(resource)?.ok_or(Error::MissingId)?),
+    };
+
+    Ok((kind, identity))
+}
+
+fn resource_lid(resource: &Value) -> Result<Option<&str>, Error> {
+    match resource.get("lid") {
+        Some(lid) => lid
+            .as_str()
+            .map(Some)
+            .ok_or(Error::InvalidType("Expected a string")),
+        None => Ok(None),
+    }
+}
+
+/// A field type which a relationship with a `resource` can be resolved into.
+pub trait RelationshipTarget<'gc>: Sized {
+    fn resolve(
+        included_map: &mut IncludedMap<'_, 'gc>,
+        reference: &LocalReference,
+    ) -> Result<Self, Error>;
+}
+
+impl<'gc, T> RelationshipTarget<'gc> for &'gc T
+where
+    T: JsonApiDeserialize<'gc> + 'gc,
+{
+    fn resolve(
+        included_map: &mut IncludedMap<'_, 'gc>,
+        reference: &LocalReference,
+    ) -> Result<Self, Error> {
+        included_map.get_reference::<T>(reference)
+    }
+}
+
+macro_rules! shared_relationship_target {
+    ($pointer:ident, $weak:ident) => {
+        impl<'gc, T> RelationshipTarget<'gc> for $pointer<T>
+        where
+            T: JsonApiDeserialize<'gc> + 'static,
+        {
+            fn resolve(
+                included_map: &mut IncludedMap<'_, 'gc>,
+                reference: &LocalReference,
+            ) -> Result<Self, Error> {
+                let identity = included_map.reference_identity(reference)?;
+                included_map.get_shared::<T, Self>(&reference.kind, identity)
+            }
+        }
+
+        impl<'gc, T> RelationshipTarget<'gc> for $weak<T>
+        where
+            T: JsonApiDeserialize<'gc> + 'static,
+        {
+            fn resolve(
+                included_map: &mut IncludedMap<'_, 'gc>,
+                reference: &LocalReference,
+            ) -> Result<Self, Error> {
+                let identity = included_map.reference_identity(reference)?;
+                included_map.get_shared_weak::<T, $pointer<T>>(&reference.kind, identity)
+            }
+        }
+    };
+}
+
+shared_relationship_target!(Arc, ArcWeak);
+shared_relationship_target!(Rc, RcWeak);
@@ -0,0 +1,102 @@
+use crate::deserialize::{Error, JsonApiDeserialize};
+use crate::document::LocalReference;
+use crate::included::{DocumentIndex, IncludedMap, RelationshipTarget};
+use std::cell::OnceCell;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// A relationship which is only deserialized once it is accessed for the first time.
+///
+/// The linkage is checked against the document right away, but the resource itself is only
+/// deserialized on the first call to [`Lazy::get`], and then cached in the arena of the document.
+/// As the resource is not deserialized up front, accessing it may fail.
+pub struct Lazy<'gc, T> {
+    reference: LocalReference,
+    index: Option<DocumentIndex<'gc>>,
+    // Type-erased, as a `OnceCell<&'gc T>` would make the relationship invariant in `'gc`. It is
+    // only ever set to a resource in the arena, which outlives `'gc`.
+    resource: OnceCell<NonNull<()>>,
+    _resource: PhantomData<&'gc T>,
+}
+
+impl<'gc, T> Lazy<'gc, T>
+where
+    T: JsonApiDeserialize<'gc> + 'gc,
+{
+    pub fn reference(&self) -> &LocalReference {
+        &self.reference
+    }
+
+    /// Whether the resource has been deserialized already.
+    pub fn is_loaded(&self) -> bool {
+        self.resource.get().is_some()
+    }
+
+    /// Returns the resource, deserializing it if this is the first access.
+    pub fn get(&self) -> Result<&'gc T, Error> {
+        if let Some(resource) = self.loaded() {
+            return Ok(resource);
+        }
+
+        // Without an index the resource is always deserialized right away.
+        let index = self
+            .index
+            .expect("a lazy relationship without index is always loaded");
+        let resource = index.lock().get_reference::<T>(&self.reference)?;
+        self.resource.get_or_init(|| NonNull::from(resource).cast());
+
+        Ok(resource)
+    }
+}
+
+impl<'gc, T> Lazy<'gc, T> {
+    fn loaded(&self) -> Option<&'gc T> {
+        // SAFETY: The cell only ever holds a `&'gc T`, see `Lazy::resource`.
+        self.resource
+            .get()
+            .map(|resource| unsafe { resource.cast::<T>().as_ref() })
+    }
+}
+
+impl<'gc, T: Debug> Debug for Lazy<'gc, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lazy")
+            .field("reference", &self.reference)
+            .field("resource", &self.loaded())
+            .finish()
+    }
+}
+
+impl<'gc, T> RelationshipTarget<'gc> for Lazy<'gc, T>
+where
+    T: JsonApiDeserialize<'gc> + 'gc,
+{
+    fn resolve(
+        included_map: &mut IncludedMap<'_, 'gc>,
+        reference: &LocalReference,
+    ) -> Result<Self, Error> {
+        let Some(index) = included_map.index() else {
+            // The identity map does not outlive deserialization, so resolve eagerly instead.
+            let resource = included_map.get_reference::<T>(reference)?;
+
+            return Ok(Self {
+                reference: reference.clone(),
+                index: None,
+                resource: OnceCell::from(NonNull::from(resource).cast()),
+                _resource: PhantomData,
+            });
+        };
+
+        // The resource is only deserialized on first access, but it is used all the same.
+        let identity = included_map.reference_identity(reference)?;
+        included_map.reach(&reference.kind, identity)?;
+
+        Ok(Self {
+            reference: reference.clone(),
+            index: Some(index),
+            resource: OnceCell::new(),
+            _resource: PhantomData,
+        })
+    }
+}
@@ -1,24 +1,66 @@
+mod any_of;
 mod deserialize;
+#[cfg(feature = "miette")]
+mod diagnostic;
 mod document;
+mod drift;
 mod error;
+mod extension;
+mod field;
+mod holder;
 mod included;
+mod lazy;
 mod link;
+mod lossy;
+mod maybe_included;
+mod member_name;
+mod options;
+mod owned;
+mod path;
+mod policy;
+mod relationship;
+mod shared;
+mod span;
+mod typed_error;
 
-pub use deserialize::{deserialize_document, Error as DeserializeError, JsonApiDeserialize};
+pub use any_of::{AnyOf2, AnyOf3, AnyOf4, AnyOf5, AnyOf6, IgnoreUnknown};
+pub use deserialize::{
+    collect_results, deserialize_document, deserialize_document_in, deserialize_document_with_meta,
+    deserialize_document_with_options, deserialize_error_document, deserialize_shared_document,
+    deserialize_shared_document_with_options, CycleStub, DefaultCycleStub,
+    Error as DeserializeError, JsonApiDeserialize, NoCycleStub, Rebind,
+};
+#[cfg(feature = "miette")]
+pub use diagnostic::SourceDiagnostic;
 pub use document::{
-    Document, DocumentError, DocumentLinks, ErrorLinks, ErrorSource, RawMultipleRelationship,
-    RawOptionalRelationship, RawSingleRelationship, Reference,
+    Document, DocumentError, DocumentLinks, ErrorDocument, ErrorLinks, ErrorSource, JsonApiObject,
+    LocalReference, RawMultipleRelationship, RawOptionalRelationship, RawSingleRelationship,
+    Reference, ResourceIdentifier, ResourceLinks, SharedDocument,
 };
+pub use drift::{DriftReport, UnmappedMember};
 pub use error::Error;
-pub use included::IncludedMap;
+pub use field::{Field, FieldKind, FieldPath};
+pub use holder::{Arena, Holder, SyncHolder};
+pub use included::{IncludedMap, RelationshipTarget};
+pub use lazy::Lazy;
 pub use link::Link;
+pub use lossy::Lossy;
+pub use maybe_included::MaybeIncluded;
+pub use options::DeserializeOptions;
+pub use owned::{
+    deserialize_owned_document, deserialize_owned_document_with_options, deserialize_sync_document,
+    deserialize_sync_document_with_options, OwnedDocument,
+};
+pub use path::{ErrorPath, Location, PathSegment};
+pub use policy::{DeviationPolicy, Leniency, Warning};
+pub use relationship::{RawRelationship, Relationship, RelationshipLinkage, RelationshipLinks};
+pub use span::Span;
+pub use typed_error::JsonApiError;
 
 extern crate jsonapi_deserialize_derive;
-pub use jsonapi_deserialize_derive::JsonApiDeserialize;
+pub use jsonapi_deserialize_derive::{JsonApiDeserialize, JsonApiError};
 
-#[doc(hidden)]
-pub extern crate zonbi;
 #[doc(hidden)]
 pub extern crate bumpalo;
-
-pub use document::Holder;
\ No newline at end of file
+#[doc(hidden)]
+pub extern crate zonbi;
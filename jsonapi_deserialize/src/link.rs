@@ -90,6 +90,20 @@ where
     })
 }
 
+pub(crate) fn deserialize_uris<'de, D>(deserializer: D) -> Result<Vec<Uri>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Vec<String> = Deserialize::deserialize(deserializer)?;
+    raw.iter()
+        .map(|raw| {
+            Uri::from_str(raw).map_err(|error| {
+                de::Error::invalid_value(Unexpected::Str(&error.to_string()), &"URI")
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,107 @@
+use crate::deserialize::{Error, JsonApiDeserialize, Rebind};
+use crate::field::Field;
+use crate::included::IncludedMap;
+use serde_json::Value;
+use std::ops::Deref;
+
+/// A collection which skips elements that fail to deserialize, instead of failing as a whole.
+///
+/// Used as `Lossy<Vec<T>>` for primary data or a `multiple` relationship. The elements which
+/// deserialize are kept in order, and the errors of the others are available through
+/// [`Lossy::errors`]. A value which is not an array at all still fails to deserialize.
+#[derive(Debug)]
+pub struct Lossy<C> {
+    value: C,
+    errors: Vec<Error>,
+}
+
+impl<C> Lossy<C> {
+    pub fn new(value: C, errors: Vec<Error>) -> Self {
+        Self { value, errors }
+    }
+
+    /// The errors of the elements which were skipped.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Whether no element was skipped.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_inner(self) -> C {
+        self.value
+    }
+
+    pub fn into_parts(self) -> (C, Vec<Error>) {
+        (self.value, self.errors)
+    }
+
+    /// Applies `f` to the errors of the skipped elements, e.g. to annotate them with a path.
+    #[doc(hidden)]
+    pub fn map_errors(self, f: impl FnMut(Error) -> Error) -> Self {
+        Self {
+            value: self.value,
+            errors: self.errors.into_iter().map(f).collect(),
+        }
+    }
+}
+
+impl<C: Default> Default for Lossy<C> {
+    fn default() -> Self {
+        Self::new(C::default(), Vec::new())
+    }
+}
+
+impl<C> Deref for Lossy<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> FromIterator<Result<T, Error>> for Lossy<Vec<T>> {
+    fn from_iter<I: IntoIterator<Item = Result<T, Error>>>(iter: I) -> Self {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in iter {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        Self::new(values, errors)
+    }
+}
+
+unsafe impl<T: Rebind> Rebind for Lossy<Vec<T>> {
+    type Rebound<'gc> = Lossy<Vec<T::Rebound<'gc>>>;
+}
+
+impl<'gc, T> JsonApiDeserialize<'gc> for Lossy<Vec<T>>
+where
+    T: JsonApiDeserialize<'gc>,
+{
+    type ErasedLifetime = Lossy<Vec<T::ErasedLifetime>>;
+
+    fn from_value(value: &Value, included_map: &mut IncludedMap<'_, 'gc>) -> Result<Self, Error> {
+        Ok(value
+            .as_array()
+            .ok_or(Error::InvalidType("Expected an array"))?
+            .iter()
+            .map(|value| T::from_value(value, included_map))
+            .collect())
+    }
+
+    fn accepts_resource_type(kind: &str) -> bool {
+        T::accepts_resource_type(kind)
+    }
+
+    fn fields() -> &'static [Field] {
+        T::fields()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::deserialize::{Error, JsonApiDeserialize};
+use crate::document::LocalReference;
+use crate::included::{IncludedMap, RelationshipTarget};
+
+/// A relationship whose resource may or may not be included in the document.
+///
+/// This allows the same struct to be used whether or not the client asked for the relationship
+/// to be included, e.g. through `include=author`.
+#[derive(Debug)]
+pub enum MaybeIncluded<'a, T> {
+    Included(&'a T),
+    NotIncluded(LocalReference),
+}
+
+impl<'a, T> MaybeIncluded<'a, T> {
+    pub fn is_included(&self) -> bool {
+        matches!(self, Self::Included(_))
+    }
+
+    /// Returns the resource, if it was included.
+    pub fn included(&self) -> Option<&'a T> {
+        match self {
+            Self::Included(resource) => Some(resource),
+            Self::NotIncluded(_) => None,
+        }
+    }
+
+    /// Returns the reference to the resource, if it was not included.
+    pub fn not_included(&self) -> Option<&LocalReference> {
+        match self {
+            Self::Included(_) => None,
+            Self::NotIncluded(reference) => Some(reference),
+        }
+    }
+}
+
+impl<'a, T> Clone for MaybeIncluded<'a, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Included(resource) => Self::Included(resource),
+            Self::NotIncluded(reference) => Self::NotIncluded(reference.clone()),
+        }
+    }
+}
+
+impl<'gc, T> RelationshipTarget<'gc> for MaybeIncluded<'gc, T>
+where
+    T: JsonApiDeserialize<'gc> + 'gc,
+{
+    fn resolve(
+        included_map: &mut IncludedMap<'_, 'gc>,
+        reference: &LocalReference,
+    ) -> Result<Self, Error> {
+        let identity = included_map.reference_identity(reference)?;
+        if !included_map.contains_identified(&reference.kind, identity) {
+            return Ok(Self::NotIncluded(reference.clone()));
+        }
+
+        included_map
+            .get_identified::<T>(&reference.kind, identity)
+            .map(Self::Included)
+    }
+}
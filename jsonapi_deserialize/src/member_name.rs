@@ -0,0 +1,25 @@
+//! Validation of member names, which the derive macro follows for the extension members it accepts.
+
+/// Whether `namespace` is a valid extension namespace, which must consist of ASCII letters and
+/// digits only.
+fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty() && namespace.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Whether `member` is a valid member name, as far as the characters allowed in any position go.
+fn is_valid_member_name(member: &str) -> bool {
+    let globally_allowed = |c: char| c.is_ascii_alphanumeric() || !c.is_ascii();
+
+    member.starts_with(globally_allowed)
+        && member.ends_with(globally_allowed)
+        && member
+            .chars()
+            .all(|c| globally_allowed(c) || c == '-' || c == '_' || c == ' ')
+}
+
+/// Splits an extension member name like `version:id` into its namespace and member.
+pub(crate) fn parse_extension_member(name: &str) -> Option<(&str, &str)> {
+    let (namespace, member) = name.split_once(':')?;
+
+    (is_valid_namespace(namespace) && is_valid_member_name(member)).then_some((namespace, member))
+}
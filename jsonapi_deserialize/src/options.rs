@@ -0,0 +1,50 @@
+use crate::policy::DeviationPolicy;
+
+/// Options controlling how documents are deserialized.
+#[derive(Debug, Clone, Default)]
+pub struct DeserializeOptions {
+    pub(crate) supported_extensions: Option<Vec<String>>,
+    pub(crate) collect_errors: bool,
+    pub(crate) report_drift: bool,
+    pub(crate) policy: DeviationPolicy,
+}
+
+impl DeserializeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects documents which declare any extension in their `jsonapi` object other than the
+    /// given ones, identified by their URI.
+    ///
+    /// By default, documents are accepted regardless of the extensions they declare.
+    pub fn supported_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.supported_extensions = Some(extensions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Keeps deserializing after a field fails, returning all errors in the document as
+    /// [`Error::Multiple`](crate::DeserializeError::Multiple) instead of only the first one.
+    pub fn collect_errors(mut self, collect_errors: bool) -> Self {
+        self.collect_errors = collect_errors;
+        self
+    }
+
+    /// Tracks which parts of the document the types use, to report the rest as a
+    /// [`DriftReport`](crate::DriftReport) on the deserialized document.
+    pub fn report_drift(mut self, report_drift: bool) -> Self {
+        self.report_drift = report_drift;
+        self
+    }
+
+    /// Decides which deviations from the specification are rejected, tolerated with a
+    /// [`Warning`](crate::Warning) on the deserialized document, or silently accepted.
+    pub fn deviation_policy(mut self, policy: DeviationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
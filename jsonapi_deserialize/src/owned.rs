@@ -0,0 +1,201 @@
+use crate::deserialize::{deserialize_document_in, Error, Rebind};
+use crate::document::{Document, DocumentLinks, JsonApiObject, ResourceIdentifier};
+use crate::holder::{Arena, Holder, SyncHolder};
+use crate::options::DeserializeOptions;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+/// A [`Document`] bundled together with the [`Holder`] its resources are allocated in.
+///
+/// Unlike a [`Document`], this can be returned from functions, stored in structs and cached,
+/// since it does not borrow from anything. `T` is the lifetime-erased form of the primary data,
+/// e.g. `OwnedDocument<Article<'static>>`; accessors hand it out bound to the borrow of `self`.
+///
+/// When created through [`deserialize_sync_document`], the document is backed by a
+/// [`SyncHolder`] and can be sent to, and shared with, other threads. Like with a [`Document`],
+/// `M` is the type the top-level `meta` is deserialized into.
+pub struct OwnedDocument<T: Rebind, H: Arena = Holder, M = HashMap<String, Value>> {
+    // Dropped manually, before the arena it points into.
+    document: ManuallyDrop<Document<'static, T::Rebound<'static>, M>>,
+    // Kept as a raw pointer rather than a `Box`, as moving a `Box` would assert unique access to
+    // the arena, which the document still borrows from.
+    holder: NonNull<H>,
+}
+
+// SAFETY: The arena is only reachable through the document, and `deserialize_sync_document`
+// requires all resources reachable from `T` to be thread-safe.
+unsafe impl<T: Rebind + Send + Sync, M: Send> Send for OwnedDocument<T, SyncHolder, M> {}
+unsafe impl<T: Rebind + Sync, M: Sync> Sync for OwnedDocument<T, SyncHolder, M> {}
+
+impl<T: Rebind, H: Arena, M> OwnedDocument<T, H, M> {
+    fn rebound(&self) -> &Document<'_, T::Rebound<'_>, M> {
+        let document: *const Document<'static, T::Rebound<'static>, M> = &*self.document;
+
+        // SAFETY: `Rebind` guarantees that both types only differ in their lifetimes, and that
+        // the primary data is covariant in them, as is the document itself. The shortened
+        // lifetime cannot outlive the holder, which is owned by `self`.
+        unsafe { &*document.cast() }
+    }
+
+    pub fn data(&self) -> &T::Rebound<'_> {
+        self.rebound().data
+    }
+
+    pub fn meta(&self) -> Option<&M> {
+        self.document.meta.as_ref()
+    }
+
+    pub fn links(&self) -> Option<&DocumentLinks> {
+        self.document.links.as_ref()
+    }
+
+    pub fn jsonapi(&self) -> Option<&JsonApiObject> {
+        self.document.jsonapi.as_ref()
+    }
+
+    pub fn extensions(&self) -> &HashMap<String, Value> {
+        &self.document.extensions
+    }
+
+    /// Returns the raw resource object with the given type and ID, see [`Document::find`].
+    pub fn find(&self, kind: &str, id: &str) -> Option<&Value> {
+        self.rebound().find(kind, id)
+    }
+}
+
+impl<T: Rebind, M> OwnedDocument<T, Holder, M> {
+    pub fn document(&self) -> &Document<'_, T::Rebound<'_>, M> {
+        self.rebound()
+    }
+
+    /// Resolves a reference to a resource in the document, see [`Document::resolve`].
+    pub fn resolve<R: Rebind>(
+        &self,
+        reference: &impl ResourceIdentifier,
+    ) -> Result<&R::Rebound<'_>, Error> {
+        self.rebound().resolve::<R::Rebound<'_>>(reference)
+    }
+
+    /// Returns every included resource of a type, see [`Document::included_of`].
+    pub fn included_of<R: Rebind>(
+        &self,
+    ) -> impl Iterator<Item = Result<&R::Rebound<'_>, Error>> + '_ {
+        self.rebound().included_of::<R::Rebound<'_>>()
+    }
+}
+
+// Resources resolved after deserialization may be accessed from any thread the document is shared
+// with, so unlike with a `Holder`, they have to be thread-safe as well.
+impl<T: Rebind, M> OwnedDocument<T, SyncHolder, M> {
+    /// Resolves a reference to a resource in the document, see [`Document::resolve`].
+    pub fn resolve<R: Rebind + Send + Sync>(
+        &self,
+        reference: &impl ResourceIdentifier,
+    ) -> Result<&R::Rebound<'_>, Error> {
+        self.rebound().resolve::<R::Rebound<'_>>(reference)
+    }
+
+    /// Returns every included resource of a type, see [`Document::included_of`].
+    pub fn included_of<R: Rebind + Send + Sync>(
+        &self,
+    ) -> impl Iterator<Item = Result<&R::Rebound<'_>, Error>> + '_ {
+        self.rebound().included_of::<R::Rebound<'_>>()
+    }
+}
+
+impl<T: Rebind, H: Arena, M> Drop for OwnedDocument<T, H, M> {
+    fn drop(&mut self) {
+        // SAFETY: The document is not accessed anymore, and the holder was leaked from a `Box` in
+        // `deserialize_owned`, with nothing borrowing from it once the document is gone.
+        unsafe {
+            ManuallyDrop::drop(&mut self.document);
+            drop(Box::from_raw(self.holder.as_ptr()));
+        }
+    }
+}
+
+impl<T: Rebind, H: Arena, M: Debug> Debug for OwnedDocument<T, H, M>
+where
+    T::Rebound<'static>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OwnedDocument")
+            .field("document", &*self.document)
+            .finish()
+    }
+}
+
+fn deserialize_owned<T: Rebind, H: Arena + Default + 'static, M: DeserializeOwned>(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<OwnedDocument<T, H, M>, crate::error::Error> {
+    let holder = NonNull::from(Box::leak(Box::new(H::default())));
+
+    // SAFETY: The holder lives on the heap and is kept alive alongside the document, which
+    // never hands out references outliving `self`.
+    let result = deserialize_document_in(json, unsafe { holder.as_ref() }, options);
+
+    match result {
+        Ok(document) => Ok(OwnedDocument {
+            document: ManuallyDrop::new(document),
+            holder,
+        }),
+        Err(error) => {
+            // SAFETY: Nothing borrows from the holder after a failure.
+            drop(unsafe { Box::from_raw(holder.as_ptr()) });
+
+            Err(error)
+        }
+    }
+}
+
+/// Deserializes a document into an [`OwnedDocument`], which manages its own [`Holder`].
+pub fn deserialize_owned_document<T, M>(
+    json: &str,
+) -> Result<OwnedDocument<T, Holder, M>, crate::error::Error>
+where
+    T: Rebind,
+    M: DeserializeOwned,
+{
+    deserialize_owned(json, &DeserializeOptions::default())
+}
+
+pub fn deserialize_owned_document_with_options<T, M>(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<OwnedDocument<T, Holder, M>, crate::error::Error>
+where
+    T: Rebind,
+    M: DeserializeOwned,
+{
+    deserialize_owned(json, options)
+}
+
+/// Deserializes a document into an [`OwnedDocument`] which can cross thread boundaries.
+///
+/// The primary data must be `Send + Sync`, which in turn requires every resource it refers to
+/// to be `Sync`.
+pub fn deserialize_sync_document<T, M>(
+    json: &str,
+) -> Result<OwnedDocument<T, SyncHolder, M>, crate::error::Error>
+where
+    T: Rebind + Send + Sync,
+    M: DeserializeOwned + Send + Sync,
+{
+    deserialize_owned(json, &DeserializeOptions::default())
+}
+
+pub fn deserialize_sync_document_with_options<T, M>(
+    json: &str,
+    options: &DeserializeOptions,
+) -> Result<OwnedDocument<T, SyncHolder, M>, crate::error::Error>
+where
+    T: Rebind + Send + Sync,
+    M: DeserializeOwned + Send + Sync,
+{
+    deserialize_owned(json, options)
+}
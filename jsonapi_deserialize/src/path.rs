@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter};
+
+/// Whether a resource is part of the primary data or of the included resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Data,
+    Included,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A resource, identified by its `type` and `id`, or its `lid` if it has no `id`.
+    Resource {
+        location: Location,
+        kind: String,
+        id: String,
+    },
+    /// A member of a resource or of one of its members, e.g. `attributes` or `name`.
+    Member(String),
+}
+
+/// The location of a deserialization error within a document.
+///
+/// Renders like `data[article:a-1].relationships.author -> included[person:p-1].attributes.name`,
+/// where each `->` follows a relationship to another resource.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl ErrorPath {
+    /// The resources traversed to reach the error, starting with the outermost one.
+    pub fn resources(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.segments.iter().filter_map(|segment| match segment {
+            PathSegment::Resource { kind, id, .. } => Some((kind.as_str(), id.as_str())),
+            PathSegment::Member(_) => None,
+        })
+    }
+
+    /// The name of the innermost member, e.g. `name` for `included[person:p-1].attributes.name`.
+    pub fn member(&self) -> Option<&str> {
+        match self.segments.last()? {
+            PathSegment::Member(member) => Some(member),
+            PathSegment::Resource { .. } => None,
+        }
+    }
+
+    /// Renders the path as a prefix of an error message, if there is one.
+    pub(crate) fn prefix(&self) -> String {
+        if self.segments.is_empty() {
+            return String::new();
+        }
+
+        format!("{}: ", self)
+    }
+}
+
+impl Display for ErrorPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Resource { location, kind, id } => {
+                    if index > 0 {
+                        write!(f, " -> ")?;
+                    }
+
+                    let location = match location {
+                        Location::Data => "data",
+                        Location::Included => "included",
+                    };
+                    write!(f, "{}[{}:{}]", location, kind, id)?;
+                }
+                PathSegment::Member(member) => {
+                    if index > 0 {
+                        write!(f, ".")?;
+                    }
+
+                    write!(f, "{}", member)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use crate::deserialize::Error;
+use crate::document::{DocumentError, RawDocument};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// How a deviation from the specification is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leniency {
+    /// Fails deserialization of the document.
+    Reject,
+    /// Deserializes the document anyway, recording a [`Warning`] on it.
+    Warn,
+    /// Deserializes the document anyway.
+    Accept,
+}
+
+/// Decides how deviations from the specification, commonly sent by sloppy servers, are
+/// handled.
+///
+/// The default policy matches how documents are handled without one: numeric IDs and documents
+/// containing both `data` and `errors` are rejected, while duplicate included resources are
+/// accepted, with the last one taking precedence.
+#[derive(Debug, Clone)]
+pub struct DeviationPolicy {
+    pub(crate) numeric_ids: Leniency,
+    pub(crate) duplicate_included: Leniency,
+    pub(crate) data_and_errors: Leniency,
+}
+
+impl Default for DeviationPolicy {
+    fn default() -> Self {
+        Self {
+            numeric_ids: Leniency::Reject,
+            duplicate_included: Leniency::Accept,
+            data_and_errors: Leniency::Reject,
+        }
+    }
+}
+
+impl DeviationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects every deviation.
+    pub fn strict() -> Self {
+        Self {
+            numeric_ids: Leniency::Reject,
+            duplicate_included: Leniency::Reject,
+            data_and_errors: Leniency::Reject,
+        }
+    }
+
+    /// Tolerates every deviation, recording a warning for each.
+    pub fn lenient() -> Self {
+        Self {
+            numeric_ids: Leniency::Warn,
+            duplicate_included: Leniency::Warn,
+            data_and_errors: Leniency::Warn,
+        }
+    }
+
+    /// Resources and resource identifiers whose `id` is a number instead of a string.
+    ///
+    /// When tolerated, the number is read as a string, e.g. `"42"` for `42`.
+    pub fn numeric_ids(mut self, leniency: Leniency) -> Self {
+        self.numeric_ids = leniency;
+        self
+    }
+
+    /// Resources which are included more than once.
+    ///
+    /// When tolerated, the last one takes precedence. When rejected, deserialization fails with
+    /// [`Error::DuplicateResource`](crate::DeserializeError::DuplicateResource).
+    pub fn duplicate_included(mut self, leniency: Leniency) -> Self {
+        self.duplicate_included = leniency;
+        self
+    }
+
+    /// Documents containing both `data` and `errors`.
+    ///
+    /// When tolerated, the `data` is deserialized and the errors are ignored. When rejected, the
+    /// document is treated as an error document.
+    pub fn data_and_errors(mut self, leniency: Leniency) -> Self {
+        self.data_and_errors = leniency;
+        self
+    }
+}
+
+/// A deviation from the specification which was tolerated while deserializing a document.
+#[derive(Debug)]
+pub enum Warning {
+    /// A resource or resource identifier with a numeric `id`, which was read as a string.
+    NumericId { kind: String, id: String },
+    /// A resource which is included more than once.
+    DuplicateIncluded { kind: String, id: String },
+    /// Errors which were sent next to the `data` of the document, and ignored.
+    DataAndErrors(Vec<DocumentError>),
+}
+
+/// Applies the policy to a parsed document, returning the warnings for tolerated deviations.
+pub(crate) fn apply_policy(
+    raw_document: &mut RawDocument,
+    policy: &DeviationPolicy,
+) -> Result<Vec<Warning>, Error> {
+    let mut warnings = Vec::new();
+
+    if raw_document.data.is_some() && policy.data_and_errors != Leniency::Reject {
+        if let Some(errors) = raw_document.errors.take() {
+            if policy.data_and_errors == Leniency::Warn {
+                warnings.push(Warning::DataAndErrors(errors));
+            }
+        }
+    }
+
+    // An error document is returned as such, regardless of any other deviation.
+    if raw_document.errors.is_some() {
+        return Ok(warnings);
+    }
+
+    if policy.numeric_ids != Leniency::Reject {
+        let mut numeric_ids = HashSet::new();
+        let resources = match &mut raw_document.data {
+            Some(Value::Array(resources)) => resources.iter_mut().collect::<Vec<_>>(),
+            Some(resource) => vec![resource],
+            None => Vec::new(),
+        };
+
+        for resource in resources
+            .into_iter()
+            .chain(raw_document.included.iter_mut().flatten())
+        {
+            stringify_ids(resource, &mut numeric_ids);
+        }
+
+        if policy.numeric_ids == Leniency::Warn {
+            let mut numeric_ids = numeric_ids.into_iter().collect::<Vec<_>>();
+            numeric_ids.sort();
+
+            warnings.extend(
+                numeric_ids
+                    .into_iter()
+                    .map(|(kind, id)| Warning::NumericId { kind, id }),
+            );
+        }
+    }
+
+    if policy.duplicate_included != Leniency::Accept {
+        let mut seen = HashSet::new();
+
+        for resource in raw_document.included.iter().flatten() {
+            let Some((kind, member, id)) = identifier(resource) else {
+                continue;
+            };
+
+            if seen.insert((kind, member, id)) {
+                continue;
+            }
+
+            let (kind, id) = (kind.to_string(), id.to_string());
+
+            match policy.duplicate_included {
+                Leniency::Reject => return Err(Error::DuplicateResource { kind, id }),
+                _ => warnings.push(Warning::DuplicateIncluded { kind, id }),
+            }
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Turns numeric IDs of a resource and the resource identifiers in its relationships into
+/// strings, collecting the identifiers which were changed.
+fn stringify_ids(resource: &mut Value, numeric_ids: &mut HashSet<(String, String)>) {
+    stringify_id(resource, numeric_ids);
+
+    let relationships = resource
+        .get_mut("relationships")
+        .and_then(Value::as_object_mut);
+
+    for relationship in relationships
+        .into_iter()
+        .flat_map(|object| object.values_mut())
+    {
+        match relationship.get_mut("data") {
+            Some(Value::Array(identifiers)) => {
+                for identifier in identifiers {
+                    stringify_id(identifier, numeric_ids);
+                }
+            }
+            Some(identifier) => stringify_id(identifier, numeric_ids),
+            None => {}
+        }
+    }
+}
+
+fn stringify_id(identifier: &mut Value, numeric_ids: &mut HashSet<(String, String)>) {
+    let Some(object) = identifier.as_object_mut() else {
+        return;
+    };
+    let Some(Value::Number(number)) = object.get("id") else {
+        return;
+    };
+
+    let id = number.to_string();
+    let kind = object
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    object.insert("id".to_string(), Value::String(id.clone()));
+    numeric_ids.insert((kind, id));
+}
+
+/// Returns the `type` of a resource and what identifies it, its `id` or if it has none its `lid`.
+fn identifier(resource: &Value) -> Option<(&str, &'static str, &str)> {
+    let kind = resource.get("type")?.as_str()?;
+    let member = if resource.get("id").is_some() {
+        "id"
+    } else {
+        "lid"
+    };
+    let id = resource.get(member)?.as_str()?;
+
+    Some((kind, member, id))
+}
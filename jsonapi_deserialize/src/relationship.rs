@@ -0,0 +1,79 @@
+use crate::document::{RawMultipleRelationship, RawOptionalRelationship, RawSingleRelationship};
+use crate::link::Link;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A relationship field which keeps the `links` and `meta` of the relationship object, next to
+/// its linkage.
+///
+/// The field must be marked as `relationship_object`. `D` is what the field would be typed as
+/// otherwise, e.g. `Reference`, `Option<&'a Person>` or `Vec<&'a Comment<'a>>`. The linkage is
+/// `None` if the relationship object has no `data` member, which is common for relationships which
+/// were not included in the response.
+#[derive(Debug)]
+pub struct Relationship<D, M = HashMap<String, Value>> {
+    pub data: Option<D>,
+    pub links: Option<RelationshipLinks>,
+    pub meta: Option<M>,
+}
+
+impl<D, M> Relationship<D, M> {
+    #[doc(hidden)]
+    pub fn new(data: Option<D>, links: Option<RelationshipLinks>, meta: Option<M>) -> Self {
+        Self { data, links, meta }
+    }
+}
+
+impl<D, M> Default for Relationship<D, M> {
+    fn default() -> Self {
+        Self::new(None, None, None)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RelationshipLinks {
+    #[serde(rename = "self")]
+    pub this: Option<Link>,
+    pub related: Option<Link>,
+    pub first: Option<Link>,
+    pub last: Option<Link>,
+    pub prev: Option<Link>,
+    pub next: Option<Link>,
+}
+
+/// A relationship object whose `data` member may be missing, as opposed to being `null`.
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "L: Deserialize<'de>, M: Deserialize<'de>"))]
+pub struct RawRelationship<L, M> {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub data: Option<L>,
+    pub links: Option<RelationshipLinks>,
+    pub meta: Option<M>,
+}
+
+fn deserialize_present<'de, D, L>(deserializer: D) -> Result<Option<L>, D::Error>
+where
+    D: Deserializer<'de>,
+    L: Deserialize<'de>,
+{
+    L::deserialize(deserializer).map(Some)
+}
+
+/// Maps the raw relationship types to the linkage they hold.
+#[doc(hidden)]
+pub trait RelationshipLinkage {
+    type Linkage;
+}
+
+impl<R> RelationshipLinkage for RawSingleRelationship<R> {
+    type Linkage = R;
+}
+
+impl<R> RelationshipLinkage for RawOptionalRelationship<R> {
+    type Linkage = Option<R>;
+}
+
+impl<R> RelationshipLinkage for RawMultipleRelationship<R> {
+    type Linkage = Vec<R>;
+}
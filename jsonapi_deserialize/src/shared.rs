@@ -0,0 +1,53 @@
+use std::mem::MaybeUninit;
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+/// A reference counted pointer which resources can be shared through, instead of an arena.
+pub(crate) trait SharedPointer<T>: Clone + 'static {
+    type Weak: Clone + 'static;
+
+    /// Builds the value while handing out a weak pointer to it, which cannot be upgraded until
+    /// the value has been built, and never if building it failed.
+    fn try_new_cyclic<E>(build: impl FnOnce(&Self::Weak) -> Result<T, E>) -> Result<Self, E>;
+
+    fn downgrade(this: &Self) -> Self::Weak;
+}
+
+macro_rules! shared_pointer {
+    ($pointer:ident, $weak:ty) => {
+        impl<T: 'static> SharedPointer<T> for $pointer<T> {
+            type Weak = $weak;
+
+            fn try_new_cyclic<E>(
+                build: impl FnOnce(&Self::Weak) -> Result<T, E>,
+            ) -> Result<Self, E> {
+                let mut result = Ok(());
+                // The strong count stays at zero until `new_cyclic` returns, so the weak pointer
+                // cannot be upgraded while the value is being built.
+                let uninit = $pointer::<MaybeUninit<T>>::new_cyclic(|weak| {
+                    // SAFETY: `MaybeUninit<T>` has the same layout as `T`.
+                    let weak = unsafe { <$weak>::from_raw(weak.clone().into_raw().cast::<T>()) };
+
+                    match build(&weak) {
+                        Ok(value) => MaybeUninit::new(value),
+                        Err(error) => {
+                            result = Err(error);
+                            MaybeUninit::uninit()
+                        }
+                    }
+                });
+
+                // SAFETY: The value was initialized unless building it failed, in which case the
+                // only strong pointer is dropped here, before anything could upgrade to it.
+                result.map(|()| unsafe { uninit.assume_init() })
+            }
+
+            fn downgrade(this: &Self) -> Self::Weak {
+                $pointer::downgrade(this)
+            }
+        }
+    };
+}
+
+shared_pointer!(Arc, sync::Weak<T>);
+shared_pointer!(Rc, rc::Weak<T>);
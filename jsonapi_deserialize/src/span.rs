@@ -0,0 +1,284 @@
+use crate::path::{ErrorPath, Location, PathSegment};
+
+/// A range of the JSON input, e.g. the value an error occurred at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// The byte offset of the start of the range.
+    pub offset: usize,
+    /// The length of the range in bytes.
+    pub len: usize,
+    /// The one-based line of the start of the range.
+    pub line: usize,
+    /// The one-based column of the start of the range, in characters.
+    pub column: usize,
+}
+
+impl Span {
+    fn new(json: &str, offset: usize, len: usize) -> Self {
+        let before = &json[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            offset,
+            len,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Returns the span of a line and column as reported by `serde_json`.
+    pub(crate) fn from_line_column(json: &str, line: usize, column: usize) -> Option<Self> {
+        if line == 0 {
+            return None;
+        }
+
+        let line_start = json
+            .split_inclusive('\n')
+            .take(line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let mut offset = (line_start + column.saturating_sub(1)).min(json.len());
+
+        while !json.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        Some(Self::new(json, offset, 0))
+    }
+
+    /// Returns the span of the value an error path points at.
+    ///
+    /// If a member along the path is missing, e.g. for a missing attribute, this is the span of
+    /// the innermost object which does exist.
+    pub(crate) fn from_path(json: &str, path: &ErrorPath) -> Option<Self> {
+        let resource = path
+            .segments
+            .iter()
+            .rposition(|segment| matches!(segment, PathSegment::Resource { .. }));
+
+        let mut tokens = Vec::new();
+        let members = match resource {
+            Some(index) => {
+                let PathSegment::Resource { location, kind, id } = &path.segments[index] else {
+                    unreachable!();
+                };
+                let location = match location {
+                    Location::Data => "data",
+                    Location::Included => "included",
+                };
+                tokens.push(Token::Member(location));
+                tokens.push(Token::Resource(kind, id));
+
+                &path.segments[index + 1..]
+            }
+            None => {
+                tokens.push(Token::Member("data"));
+                &path.segments[..]
+            }
+        };
+
+        tokens.extend(members.iter().filter_map(|segment| match segment {
+            PathSegment::Member(member) => Some(Token::Member(member)),
+            PathSegment::Resource { .. } => None,
+        }));
+
+        let scanner = Scanner {
+            json: json.as_bytes(),
+        };
+        let mut current = scanner.value(0)?;
+
+        for token in tokens {
+            match scanner.find(current, &token) {
+                Some(value) => current = value,
+                None => break,
+            }
+        }
+
+        Some(Self::new(json, current.0, current.1 - current.0))
+    }
+}
+
+enum Token<'a> {
+    Member(&'a str),
+    /// A resource with the given `type` and `id` or `lid`, either the value itself or one of its
+    /// elements.
+    Resource(&'a str, &'a str),
+}
+
+/// Locates values within a JSON document, which is known to be valid.
+struct Scanner<'a> {
+    json: &'a [u8],
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&self, mut position: usize) -> usize {
+        while self
+            .json
+            .get(position)
+            .is_some_and(|byte| byte.is_ascii_whitespace())
+        {
+            position += 1;
+        }
+
+        position
+    }
+
+    /// Returns the start and end of the value at the position, skipping leading whitespace.
+    fn value(&self, position: usize) -> Option<(usize, usize)> {
+        let start = self.skip_whitespace(position);
+
+        let end = match self.json.get(start)? {
+            b'"' => self.string_end(start)?,
+            b'{' | b'[' => {
+                let mut depth = 0;
+                let mut position = start;
+
+                loop {
+                    match self.json.get(position)? {
+                        b'"' => {
+                            position = self.string_end(position)?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+
+                            if depth == 0 {
+                                break position + 1;
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    position += 1;
+                }
+            }
+            _ => {
+                let mut position = start;
+
+                while self
+                    .json
+                    .get(position)
+                    .is_some_and(|byte| !b",}] \t\r\n".contains(byte))
+                {
+                    position += 1;
+                }
+
+                position
+            }
+        };
+
+        Some((start, end))
+    }
+
+    fn string_end(&self, start: usize) -> Option<usize> {
+        let mut position = start + 1;
+
+        loop {
+            match self.json.get(position)? {
+                b'\\' => position += 2,
+                b'"' => return Some(position + 1),
+                _ => position += 1,
+            }
+        }
+    }
+
+    fn string(&self, (start, end): (usize, usize)) -> Option<String> {
+        serde_json::from_slice(&self.json[start..end]).ok()
+    }
+
+    /// Returns the members of the object at the span, as key and value spans.
+    fn members(&self, (start, _): (usize, usize)) -> Vec<(String, (usize, usize))> {
+        let mut members = Vec::new();
+
+        if self.json.get(start) != Some(&b'{') {
+            return members;
+        }
+
+        let mut position = start + 1;
+
+        while let Some((key_start, key_end)) = self.value(position) {
+            if self.json[key_start] != b'"' {
+                break;
+            }
+
+            let Some(value) = self.value(self.skip_whitespace(key_end) + 1) else {
+                break;
+            };
+            let Some(key) = self.string((key_start, key_end)) else {
+                break;
+            };
+            members.push((key, value));
+
+            position = self.skip_whitespace(value.1);
+            if self.json.get(position) != Some(&b',') {
+                break;
+            }
+
+            position += 1;
+        }
+
+        members
+    }
+
+    fn elements(&self, (start, _): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut elements = Vec::new();
+
+        if self.json.get(start) != Some(&b'[') {
+            return elements;
+        }
+
+        let mut position = self.skip_whitespace(start + 1);
+
+        if self.json.get(position) == Some(&b']') {
+            return elements;
+        }
+
+        while let Some(element) = self.value(position) {
+            elements.push(element);
+
+            position = self.skip_whitespace(element.1);
+            if self.json.get(position) != Some(&b',') {
+                break;
+            }
+
+            position += 1;
+        }
+
+        elements
+    }
+
+    fn is_resource(&self, value: (usize, usize), kind: &str, id: &str) -> bool {
+        let members = self.members(value);
+        let member = |name: &str| {
+            members
+                .iter()
+                .rfind(|(key, _)| key == name)
+                .and_then(|(_, value)| self.string(*value))
+        };
+
+        member("type").as_deref() == Some(kind)
+            && (member("id").as_deref() == Some(id) || member("lid").as_deref() == Some(id))
+    }
+
+    /// Returns the value a token points at. Like in the deserialized document, the last of
+    /// duplicate members or resources wins.
+    fn find(&self, value: (usize, usize), token: &Token) -> Option<(usize, usize)> {
+        match token {
+            Token::Member(name) => self
+                .members(value)
+                .into_iter()
+                .rfind(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            Token::Resource(kind, id) => {
+                if self.is_resource(value, kind, id) {
+                    return Some(value);
+                }
+
+                self.elements(value)
+                    .into_iter()
+                    .rfind(|element| self.is_resource(*element, kind, id))
+            }
+        }
+    }
+}
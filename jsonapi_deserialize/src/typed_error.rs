@@ -0,0 +1,20 @@
+use crate::document::{DocumentError, ErrorDocument};
+
+/// An application specific error which the errors of an error document can be mapped to.
+///
+/// This is usually implemented with `#[derive(JsonApiError)]` on an enum, where every variant is
+/// tagged with the `code` it represents, plus a fallback variant for unknown errors.
+pub trait JsonApiError: Sized {
+    fn from_document_error(error: DocumentError) -> Self;
+
+    fn from_document_errors(errors: impl IntoIterator<Item = DocumentError>) -> Vec<Self> {
+        errors.into_iter().map(Self::from_document_error).collect()
+    }
+}
+
+impl ErrorDocument {
+    /// Maps the errors of the document to an application specific error type.
+    pub fn into_typed<E: JsonApiError>(self) -> Vec<E> {
+        E::from_document_errors(self.errors)
+    }
+}
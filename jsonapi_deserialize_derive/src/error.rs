@@ -0,0 +1,178 @@
+use darling::{ast, FromDeriveInput, FromField, FromVariant};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Generics, Type};
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(json_api), supports(enum_any))]
+struct ErrorReceiver {
+    ident: Ident,
+    generics: Generics,
+    data: ast::Data<ErrorVariantReceiver, ()>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(json_api))]
+struct ErrorVariantReceiver {
+    ident: Ident,
+    fields: ast::Fields<ErrorFieldReceiver>,
+    code: Option<String>,
+    status: Option<String>,
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(json_api))]
+struct ErrorFieldReceiver {
+    ident: Option<Ident>,
+    ty: Type,
+    #[darling(default)]
+    title: bool,
+    #[darling(default)]
+    detail: bool,
+    #[darling(default)]
+    pointer: bool,
+    #[darling(default)]
+    meta: bool,
+}
+
+pub(crate) fn impl_json_api_error(input: &DeriveInput) -> TokenStream {
+    let receiver = match ErrorReceiver::from_derive_input(input) {
+        Ok(receiver) => receiver,
+        Err(error) => return error.write_errors(),
+    };
+
+    match impl_error_enum(&receiver) {
+        Ok(tokens) => tokens,
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+fn impl_error_enum(receiver: &ErrorReceiver) -> syn::Result<TokenStream> {
+    let ident = &receiver.ident;
+    let (impl_generics, ty_generics, where_clause) = receiver.generics.split_for_impl();
+    let variants = receiver.data.as_ref().take_enum().unwrap();
+
+    let mut fallback = None;
+    let mut matchers = TokenStream::new();
+
+    for variant in variants {
+        let Some(code) = &variant.code else {
+            if fallback.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "only one variant may be without a `code`",
+                ));
+            }
+
+            if variant.fields.style != ast::Style::Tuple || variant.fields.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "the variant without a `code` must hold the unknown `DocumentError`, \
+                     e.g. in a `Box`",
+                ));
+            }
+
+            fallback = Some(&variant.ident);
+            continue;
+        };
+
+        matchers.extend(get_variant_tokens(variant, code)?);
+    }
+
+    let Some(fallback) = fallback else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "expected a variant like `Unknown(DocumentError)` for errors without a known `code`",
+        ));
+    };
+
+    let trait_path = quote! { jsonapi_deserialize::JsonApiError };
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            fn from_document_error(error: jsonapi_deserialize::DocumentError) -> Self {
+                #matchers
+
+                Self::#fallback(error.into())
+            }
+        }
+    })
+}
+
+/// Returns the variant if the error has its `code` and `status`, and all captured members can be
+/// deserialized into their fields.
+fn get_variant_tokens(variant: &ErrorVariantReceiver, code: &str) -> syn::Result<TokenStream> {
+    let variant_ident = &variant.ident;
+
+    let status_check = variant.status.as_ref().map(|status| {
+        quote! {
+            if error.status.as_deref() != Some(#status) {
+                break 'variant;
+            }
+        }
+    });
+
+    let mut captures = TokenStream::new();
+    let mut bindings = Vec::new();
+
+    for (index, field) in variant.fields.iter().enumerate() {
+        let binding = format_ident!("field_{}", index);
+        let ty = &field.ty;
+
+        let member = match (field.title, field.detail, field.pointer, field.meta) {
+            (true, false, false, false) => quote! { error.title.clone() },
+            (false, true, false, false) => quote! { error.detail.clone() },
+            (false, false, true, false) => quote! {
+                error.source.as_ref().and_then(|source| source.pointer.clone())
+            },
+            (false, false, false, true) => quote! {
+                error
+                    .meta
+                    .clone()
+                    .map(|meta| serde_json::Value::Object(meta.into_iter().collect()))
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "expected exactly one of `title`, `detail`, `pointer` or `meta`",
+                ))
+            }
+        };
+
+        // Leaves it to the field type whether the member may be omitted, e.g. for an `Option<T>`.
+        captures.extend(quote! {
+            let value = serde_json::Value::from(#member);
+            let Ok(#binding) = serde_json::from_value::<#ty>(value) else {
+                break 'variant;
+            };
+        });
+        bindings.push((field.ident.as_ref(), binding));
+    }
+
+    let construct = match variant.fields.style {
+        ast::Style::Struct => {
+            let fields = bindings
+                .iter()
+                .map(|(field, binding)| quote! { #field: #binding });
+            quote! { Self::#variant_ident { #(#fields),* } }
+        }
+        ast::Style::Tuple => {
+            let fields = bindings.iter().map(|(_, binding)| binding);
+            quote! { Self::#variant_ident(#(#fields),*) }
+        }
+        ast::Style::Unit => quote! { Self::#variant_ident },
+    };
+
+    Ok(quote! {
+        'variant: {
+            if error.code.as_deref() != Some(#code) {
+                break 'variant;
+            }
+
+            #status_check
+            #captures
+
+            return #construct;
+        }
+    })
+}
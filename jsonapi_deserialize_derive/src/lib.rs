@@ -1,9 +1,15 @@
-use darling::{ast, FromDeriveInput, FromField, FromMeta};
+use darling::{ast, FromDeriveInput, FromField, FromMeta, FromVariant};
 use heck::{ToLowerCamelCase, ToPascalCase, ToSnakeCase};
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, DeriveInput, Generics, Type};
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::{
+    parse_macro_input, DeriveInput, GenericArgument, Generics, Lifetime, PathArguments, Type,
+};
+
+mod error;
+// Shared with the runtime crate, which validates the member names found in documents.
+mod member_name;
 
 #[proc_macro_derive(JsonApiDeserialize, attributes(json_api))]
 pub fn json_api_deserialize(input: TokenStream) -> TokenStream {
@@ -11,31 +17,39 @@ pub fn json_api_deserialize(input: TokenStream) -> TokenStream {
     impl_json_api_deserialize(&input).into()
 }
 
-#[derive(Debug, FromMeta)]
+#[proc_macro_derive(JsonApiError, attributes(json_api))]
+pub fn json_api_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    error::impl_json_api_error(&input).into()
+}
+
+#[derive(Debug, Default, FromMeta)]
 #[darling(default)]
 #[allow(clippy::enum_variant_names)]
 enum RenameAll {
+    #[default]
     CamelCase,
     PascalCase,
     SnakeCase,
 }
 
-impl Default for RenameAll {
-    fn default() -> Self {
-        Self::CamelCase
-    }
-}
-
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(json_api), supports(struct_any))]
+#[darling(attributes(json_api), supports(struct_any, enum_newtype))]
 struct InputReceiver {
     ident: Ident,
-    #[allow(dead_code)]
     generics: Generics,
-    data: ast::Data<(), FieldReceiver>,
+    data: ast::Data<VariantReceiver, FieldReceiver>,
     resource_type: Option<String>,
     #[darling(default)]
     rename_all: RenameAll,
+    #[darling(default)]
+    deny_unknown_fields: bool,
+}
+
+#[derive(Debug, FromVariant)]
+struct VariantReceiver {
+    ident: Ident,
+    fields: ast::Fields<FieldReceiver>,
 }
 
 #[derive(Debug, FromMeta)]
@@ -49,7 +63,6 @@ enum Relationship {
 #[darling(attributes(json_api))]
 struct FieldReceiver {
     ident: Option<Ident>,
-    #[allow(dead_code)]
     ty: Type,
     relationship: Option<Relationship>,
     resource: Option<Type>,
@@ -58,6 +71,97 @@ struct FieldReceiver {
     default: bool,
     #[darling(default)]
     optional: bool,
+    #[darling(default)]
+    meta: bool,
+    #[darling(default)]
+    links: bool,
+    #[darling(default)]
+    lid: bool,
+    ext: Option<String>,
+    #[darling(default)]
+    relationship_object: bool,
+    #[darling(default)]
+    lossy: bool,
+}
+
+/// Returns the type argument of `ty` if it is a `wrapper<T>`, e.g. an `Option<T>`.
+fn unwrap_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    if path.path.segments.last()?.ident != wrapper {
+        return None;
+    }
+
+    type_argument(ty)
+}
+
+/// Returns the first type argument of `ty`, e.g. `T` for a `Relationship<T, M>`.
+fn type_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(arguments) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    arguments.args.iter().find_map(|argument| match argument {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Returns the linkage type of a `relationship_object` field, e.g. `&'a Person` for a
+/// `Relationship<&'a Person>` field.
+fn relationship_wrapper(field: &FieldReceiver) -> Option<&Type> {
+    if !field.relationship_object {
+        return None;
+    }
+
+    let mut ty = &field.ty;
+
+    if field.optional {
+        ty = unwrap_type(ty, "Option")?;
+    }
+
+    type_argument(ty)
+}
+
+/// Returns the element type of a `Vec<T>`, or of a `Lossy<Vec<T>>` if the field is `lossy`.
+fn collection_element(ty: &Type, lossy: bool) -> Option<&Type> {
+    let ty = if lossy { type_argument(ty)? } else { ty };
+
+    unwrap_type(ty, "Vec")
+}
+
+/// Returns the type each resource of a relationship field is resolved into, e.g. `&'a Person`
+/// for a `Vec<&'a Person>` field.
+fn relationship_element(field: &FieldReceiver) -> Option<&Type> {
+    if let Some(ty) = relationship_wrapper(field) {
+        return match field.relationship {
+            Some(Relationship::Single) => Some(ty),
+            Some(Relationship::Optional) => unwrap_type(ty, "Option"),
+            Some(Relationship::Multiple) => collection_element(ty, field.lossy),
+            None => None,
+        };
+    }
+
+    let mut ty = &field.ty;
+
+    if field.optional {
+        ty = unwrap_type(ty, "Option")?;
+    }
+
+    match field.relationship {
+        Some(Relationship::Single) if field.default => {
+            Some(unwrap_type(ty, "Option").unwrap_or(ty))
+        }
+        Some(Relationship::Single) => Some(ty),
+        Some(Relationship::Optional) => unwrap_type(ty, "Option"),
+        Some(Relationship::Multiple) => collection_element(ty, field.lossy),
+        None => None,
+    }
 }
 
 fn get_attribute_tokens(
@@ -105,29 +209,76 @@ fn get_attribute_tokens(
     tokens
 }
 
+/// Deserializes a top-level member of the resource object, e.g. `meta`.
+fn get_member_tokens(field_name: &Ident, member: &str, default: bool) -> proc_macro2::TokenStream {
+    let missing = if default {
+        quote! { Default::default() }
+    } else {
+        // Leaves it to the field type whether the member may be omitted, e.g. for an `Option<T>`.
+        quote! {
+            serde_json::from_value(serde_json::Value::Null)
+                .map_err(|_| Error::MissingField(stringify!(#field_name)))?
+        }
+    };
+
+    quote! {
+        let #field_name = match data.get(#member) {
+            Some(value) => serde_json::from_value(value.clone())?,
+            None => #missing,
+        };
+    }
+}
+
 fn get_relationship_tokens(
     field_name: &Ident,
     json_field_name: &str,
-    relationship_type: &str,
+    relationship_type: proc_macro2::TokenStream,
     default: bool,
     optional: bool,
+    wrapped: bool,
     lookup_tokens: Option<proc_macro2::TokenStream>,
 ) -> proc_macro2::TokenStream {
-    let ty = format_ident!("{}", relationship_type);
-    let ty = quote! { jsonapi_deserialize::#ty };
+    let ty = quote! { jsonapi_deserialize::#relationship_type };
+
+    // Parses the relationship object in `value` into `#field_name`.
+    let parse_tokens = if wrapped {
+        quote! {
+            let relationship = serde_json::from_value::<jsonapi_deserialize::RawRelationship<
+                <#ty as jsonapi_deserialize::RelationshipLinkage>::Linkage,
+                _,
+            >>(value)?;
+            let #field_name = match relationship.data {
+                Some(#field_name) => {
+                    #lookup_tokens
+                    Some(#field_name)
+                }
+                None => None,
+            };
+            let #field_name = jsonapi_deserialize::Relationship::new(
+                #field_name,
+                relationship.links,
+                relationship.meta,
+            );
+        }
+    } else {
+        quote! {
+            let #field_name = serde_json::from_value::<#ty>(value)?.data;
+            #lookup_tokens
+        }
+    };
 
     if !(default || optional) {
         return quote! {
-            let #field_name = serde_json::from_value::<#ty>(
-                data
+            let #field_name = {
+                let value = data
                     .get("relationships")
                     .ok_or(Error::MissingRelationships)?
                     .get(#json_field_name)
                     .ok_or(Error::MissingField(stringify!(#field_name)))?
-                    .clone(),
-            )?.data;
-
-            #lookup_tokens
+                    .clone();
+                #parse_tokens
+                #field_name
+            };
         };
     }
 
@@ -142,8 +293,7 @@ fn get_relationship_tokens(
         tokens.extend(quote! {
             let #field_name = match #field_name {
                 Some(value) => {
-                    let #field_name = serde_json::from_value::<#ty>(value)?.data;
-                    #lookup_tokens
+                    #parse_tokens
                     #field_name.into()
                 },
                 None => Default::default(),
@@ -153,8 +303,7 @@ fn get_relationship_tokens(
         tokens.extend(quote! {
             let #field_name = match #field_name {
                 Some(value) => {
-                    let #field_name = serde_json::from_value::<#ty>(value)?.data;
-                    #lookup_tokens
+                    #parse_tokens
                     Some(#field_name)
                 },
                 None => None,
@@ -167,22 +316,159 @@ fn get_relationship_tokens(
 
 fn impl_json_api_deserialize(input: &DeriveInput) -> proc_macro2::TokenStream {
     let input_receiver = InputReceiver::from_derive_input(input).unwrap();
-    let struct_name = input_receiver.ident;
+    let type_name = &input_receiver.ident;
+
+    // Resources borrow from the arena through the lifetime parameter of the type, if there is one.
+    let (gc_lifetime, type_generics, static_generics) =
+        match input_receiver.generics.lifetimes().next() {
+            Some(param) => {
+                let lifetime = param.lifetime.clone();
+                (lifetime.clone(), quote! {<#lifetime>}, quote! {<'static>})
+            }
+            None => (
+                Lifetime::new("'gc", Span::call_site()),
+                quote! {},
+                quote! {},
+            ),
+        };
+
+    // The variants are deserialized by the types they wrap, which decide about unknown fields.
+    if input_receiver.data.is_enum() && input_receiver.deny_unknown_fields {
+        return syn::Error::new_spanned(
+            type_name,
+            "`deny_unknown_fields` is not supported on enums, add it to the types of the variants",
+        )
+        .to_compile_error();
+    }
+
+    // `Rebind` requires resources to be covariant in their lifetime, which is checked here, as
+    // an invariant field would otherwise allow an `OwnedDocument` to hand out dangling references.
+    let covariance_check = input_receiver.generics.lifetimes().next().map(|_| {
+        quote! {
+            const _: () = {
+                fn covariant<'short, 'long: 'short>(
+                    resource: #type_name<'long>,
+                ) -> #type_name<'short> {
+                    resource
+                }
+            };
+        }
+    });
+
+    let (from_value_body, accepts_body, fields_body) = match &input_receiver.data {
+        ast::Data::Struct(_) => impl_struct(&input_receiver, &gc_lifetime),
+        ast::Data::Enum(variants) => impl_enum(variants, &gc_lifetime),
+    };
+
+    quote! {
+        impl<#gc_lifetime> jsonapi_deserialize::JsonApiDeserialize<#gc_lifetime> for #type_name #type_generics {
+            type ErasedLifetime = #type_name #static_generics;
+            fn from_value(
+                value: &serde_json::Value,
+                included_map: &mut jsonapi_deserialize::IncludedMap<'_, #gc_lifetime>,
+            ) -> Result<Self, jsonapi_deserialize::DeserializeError> {
+                use jsonapi_deserialize::DeserializeError as Error;
+
+                #from_value_body
+            }
+
+            fn accepts_resource_type(kind: &str) -> bool {
+                #accepts_body
+            }
+
+            fn cycle_stub() -> Option<Self> {
+                use jsonapi_deserialize::{DefaultCycleStub as _, NoCycleStub as _};
+
+                (&jsonapi_deserialize::CycleStub::<Self>(std::marker::PhantomData)).cycle_stub()
+            }
+
+            fn fields() -> &'static [jsonapi_deserialize::Field] {
+                #fields_body
+            }
+        }
+
+        unsafe impl jsonapi_deserialize::Rebind for #type_name #static_generics {
+            type Rebound<#gc_lifetime> = #type_name #type_generics;
+        }
+
+        #covariance_check
+    }
+}
+
+fn impl_enum(
+    variants: &[VariantReceiver],
+    gc_lifetime: &Lifetime,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let mut dispatch = proc_macro2::TokenStream::new();
+    let mut accepts = Vec::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let ty = &variant.fields.fields[0].ty;
+        let resource = quote! { <#ty as jsonapi_deserialize::JsonApiDeserialize<#gc_lifetime>> };
+
+        dispatch.extend(quote! {
+            if #resource::accepts_resource_type(resource_type) {
+                return #resource::from_value(value, included_map).map(Self::#variant_name);
+            }
+        });
+        accepts.push(quote! { #resource::accepts_resource_type(kind) });
+    }
+
+    let from_value_body = quote! {
+        let resource_type = value
+            .as_object()
+            .ok_or(Error::InvalidType("Expected an object"))?
+            .get("type")
+            .ok_or_else(|| Error::MissingResourceType)?
+            .as_str()
+            .ok_or(Error::InvalidType("Expected a string"))?;
+
+        #dispatch
+
+        Err(Error::ResourceTypeMismatch {
+            expected: std::any::type_name::<Self>().to_string(),
+            found: resource_type.to_string(),
+        })
+    };
+
+    // The variants have fields of their own, which one applies depends on the resource type.
+    (from_value_body, quote! { #(#accepts)||* }, quote! { &[] })
+}
+
+fn impl_struct(
+    input_receiver: &InputReceiver,
+    gc_lifetime: &Lifetime,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let struct_name = &input_receiver.ident;
     let resource_type = input_receiver
         .resource_type
+        .clone()
         .unwrap_or_else(|| struct_name.to_string().to_snake_case());
 
     let mut field_initializers = proc_macro2::TokenStream::new();
     let mut fields = proc_macro2::TokenStream::new();
+    let mut field_infos = Vec::new();
+    let mut field_names = Vec::new();
+    let mut attribute_members = Vec::new();
+    let mut relationship_members = Vec::new();
 
-    input_receiver.data.map_struct_fields(|field| {
-        let field_name = match field.ident {
-            Some(field_name) => field_name,
-            None => return,
+    for field in input_receiver.data.as_ref().take_struct().unwrap().fields {
+        let field_name = match &field.ident {
+            Some(field_name) => field_name.clone(),
+            None => continue,
         };
 
-        let json_field_name = match field.rename {
-            Some(rename) => rename,
+        let json_field_name = match &field.rename {
+            Some(rename) => rename.clone(),
             None => match input_receiver.rename_all {
                 RenameAll::CamelCase => field_name.to_string().to_lower_camel_case(),
                 RenameAll::PascalCase => field_name.to_string().to_pascal_case(),
@@ -190,61 +476,149 @@ fn impl_json_api_deserialize(input: &DeriveInput) -> proc_macro2::TokenStream {
             },
         };
 
+        let misplaced =
+            if field.lossy && !matches!(field.relationship, Some(Relationship::Multiple)) {
+                Some("`lossy` requires `relationship = \"multiple\"`")
+            } else if field.relationship_object && field.relationship.is_none() {
+                Some("`relationship_object` requires a `relationship`")
+            } else {
+                None
+            };
+
+        if let Some(message) = misplaced {
+            let error = syn::Error::new_spanned(&field.ident, message);
+
+            return (error.to_compile_error(), quote! { false }, quote! { &[] });
+        }
+
         let default = field.default;
         let optional = field.optional;
+        let wrapped = relationship_wrapper(field).is_some();
+
+        let target = field.resource.as_ref().map(|resource| {
+            let element = relationship_element(field);
+            let element = element
+                .map(|element| quote! { #element })
+                .unwrap_or_else(|| quote! { &#gc_lifetime #resource });
+
+            quote! { <#element as jsonapi_deserialize::RelationshipTarget<#gc_lifetime>>::resolve }
+        });
+
+        // Resources are resolved by their `lid` as well, while fields without a `resource` keep
+        // the linkage as the reference type they are declared with.
+        let linkage = if target.is_some() {
+            quote! { jsonapi_deserialize::LocalReference }
+        } else {
+            relationship_element(field)
+                .map(|element| quote! { #element })
+                .unwrap_or_else(|| quote! { jsonapi_deserialize::Reference })
+        };
 
         let field_tokens = match field.relationship {
-            Some(Relationship::Single) => {
-                get_relationship_tokens(
-                    &field_name,
-                    &json_field_name,
-                    "RawSingleRelationship",
-                    default,
-                    optional,
-                    field.resource.map(|resource| quote! {
-                        let #field_name = included_map.get::<#resource>(&#field_name.kind, &#field_name.id)?;
-                    }),
-                )
-            }
-            Some(Relationship::Optional) => {
-                get_relationship_tokens(
-                    &field_name,
-                    &json_field_name,
-                    "RawOptionalRelationship",
-                    default,
-                    optional,
-                    field.resource.map(|resource| quote! {
+            Some(Relationship::Single) => get_relationship_tokens(
+                &field_name,
+                &json_field_name,
+                quote! { RawSingleRelationship<#linkage> },
+                default,
+                optional,
+                wrapped,
+                target.map(|target| {
+                    quote! {
+                        let #field_name = #target(included_map, &#field_name)?;
+                    }
+                }),
+            ),
+            Some(Relationship::Optional) => get_relationship_tokens(
+                &field_name,
+                &json_field_name,
+                quote! { RawOptionalRelationship<#linkage> },
+                default,
+                optional,
+                wrapped,
+                target.map(|target| {
+                    quote! {
                         let #field_name = match #field_name {
-                            Some(data) => Some(included_map.get::<#resource>(&data.kind, &data.id)?),
+                            Some(data) => Some(#target(included_map, &data)?),
                             None => None,
                         };
-                    }),
-                )
-            }
+                    }
+                }),
+            ),
             Some(Relationship::Multiple) => {
-                get_relationship_tokens(
+                let lossy = field.lossy && target.is_some();
+                let tokens = get_relationship_tokens(
                     &field_name,
                     &json_field_name,
-                    "RawMultipleRelationship",
+                    quote! { RawMultipleRelationship<#linkage> },
                     default,
                     optional,
-                    field.resource.map(|resource| quote! {
-                        let #field_name = #field_name
-                            .into_iter()
-                            .map(|data| included_map.get::<#resource>(&data.kind, &data.id))
-                            .collect::<Result<_, _>>()?;
+                    wrapped,
+                    target.map(|target| {
+                        if lossy {
+                            // The errors of skipped resources are not propagated, so they are
+                            // annotated with the path of the relationship right away.
+                            quote! {
+                                let #field_name = #field_name
+                                    .iter()
+                                    .map(|data| #target(included_map, data))
+                                    .collect::<jsonapi_deserialize::Lossy<Vec<_>>>()
+                                    .map_errors(|error| included_map.resource_error(
+                                        resource,
+                                        error.at_members(&["relationships", #json_field_name]),
+                                    ));
+                            }
+                        } else {
+                            quote! {
+                                let collect_errors = included_map.collects_errors();
+                                let #field_name = jsonapi_deserialize::collect_results(
+                                    #field_name.iter().map(|data| #target(included_map, data)),
+                                    collect_errors,
+                                )?;
+                            }
+                        }
                     }),
-                )
+                );
+
+                if lossy {
+                    // Keeps the resource, as `value` is the relationship object further on.
+                    quote! {
+                        let resource = value;
+                        #tokens
+                    }
+                } else {
+                    tokens
+                }
+            }
+            None if field.meta => get_member_tokens(&field_name, "meta", default),
+            None if field.links => get_member_tokens(&field_name, "links", default),
+            None if field.lid => get_member_tokens(&field_name, "lid", default),
+            None if field.ext.is_some() => {
+                let member = field.ext.as_deref().unwrap_or_default();
+
+                if member_name::parse_extension_member(member).is_none() {
+                    let error = syn::Error::new_spanned(
+                        &field.ident,
+                        format!(
+                            "invalid extension member `{}`, expected `namespace:member` with an \
+                             alphanumeric namespace and a valid member name",
+                            member
+                        ),
+                    );
+
+                    return (error.to_compile_error(), quote! { false }, quote! { &[] });
+                }
+
+                get_member_tokens(&field_name, member, default)
             }
             None => {
                 if field_name == "id" {
+                    // A resource identified by its `lid` may omit the `id`, if the field allows.
                     quote! {
-                        let #field_name = serde_json::from_value(
-                            data
-                                .get("id")
-                                .ok_or_else(|| Error::MissingId)?
-                                .clone(),
-                        )?;
+                        let #field_name = match data.get("id") {
+                            Some(value) => serde_json::from_value(value.clone())?,
+                            None => serde_json::from_value(serde_json::Value::Null)
+                                .map_err(|_| Error::MissingId)?,
+                        };
                     }
                 } else {
                     get_attribute_tokens(&field_name, &json_field_name, default, optional)
@@ -252,51 +626,155 @@ fn impl_json_api_deserialize(input: &DeriveInput) -> proc_macro2::TokenStream {
             }
         };
 
-        field_initializers.extend(field_tokens);
-        fields.extend(quote! { #field_name, });
-    });
+        match field.relationship {
+            Some(_) => relationship_members.push(json_field_name.clone()),
+            None if field.meta || field.links || field.lid || field.ext.is_some() => {}
+            None if field_name == "id" => {}
+            None => attribute_members.push(json_field_name.clone()),
+        }
 
-    let gc_lifetime = quote!{'gc};
-    let (struct_generics, static_generic) = if input_receiver.generics.params.is_empty() {
-        (quote! {}, quote!{})
-    } else {
-        (quote!{<'gc>}, quote!{<'static>})
-    };
+        let (kind, member) = match field.relationship {
+            Some(_) => (quote! { Relationship }, json_field_name),
+            None if field.meta => (quote! { Meta }, "meta".to_string()),
+            None if field.links => (quote! { Links }, "links".to_string()),
+            None if field.lid => (quote! { Lid }, "lid".to_string()),
+            None if field.ext.is_some() => (quote! { Extension }, field.ext.clone().unwrap()),
+            None if field_name == "id" => (quote! { Id }, "id".to_string()),
+            None => (quote! { Attribute }, json_field_name),
+        };
+        let members = match field.relationship {
+            Some(_) => quote! { &["relationships", #member] },
+            None if field.meta || field.links || field.lid || field.ext.is_some() => {
+                quote! { &[#member] }
+            }
+            None if field_name == "id" => quote! { &[#member] },
+            None => quote! { &["attributes", #member] },
+        };
+        let name = field_name.to_string();
+        field_infos.push(quote! {
+            jsonapi_deserialize::Field {
+                name: #name,
+                member: #member,
+                kind: jsonapi_deserialize::FieldKind::#kind,
+            }
+        });
 
-    quote! {
-        impl<#gc_lifetime> jsonapi_deserialize::JsonApiDeserialize<#gc_lifetime> for #struct_name #struct_generics {
-            type ErasedLifetime = #struct_name #static_generic;
-            fn from_value(
-                value: &serde_json::Value,
-                included_map: &mut jsonapi_deserialize::IncludedMap<'_, #gc_lifetime>,
-            ) -> Result<Self, jsonapi_deserialize::DeserializeError> {
-                use jsonapi_deserialize::DeserializeError as Error;
+        // Deserializes the field in a closure, so that errors can be annotated with their path.
+        field_initializers.extend(quote! {
+            let #field_name = (|| -> Result<_, Error> {
+                #field_tokens
 
-                let data = value.as_object().ok_or(Error::InvalidType("Expected an object"))?;
+                Ok(#field_name)
+            })();
+            let #field_name = match #field_name {
+                Ok(#field_name) => Some(#field_name),
+                Err(error) => {
+                    let error = included_map.resource_error(value, error.at_members(#members));
 
-                let resource_type: String = serde_json::from_value(
-                    data
-                        .get("type")
-                        .ok_or_else(|| Error::MissingResourceType)?
-                        .clone(),
-                )?;
+                    if !included_map.collects_errors() {
+                        return Err(error);
+                    }
 
-                if resource_type != #resource_type {
-                    return Err(Error::ResourceTypeMismatch {
-                        expected: #resource_type.to_string(),
-                        found: resource_type,
-                    });
+                    field_errors.push(error);
+                    None
                 }
+            };
+        });
+        fields.extend(quote! { #field_name, });
+        field_names.push(field_name);
+    }
 
-                #field_initializers
+    // Members which no field is read from are rejected before any field is deserialized.
+    let deny_unknown_fields = if input_receiver.deny_unknown_fields {
+        quote! {
+            let known_members: [(&str, &[&str]); 2] = [
+                ("attributes", &[#(#attribute_members),*]),
+                ("relationships", &[#(#relationship_members),*]),
+            ];
 
-                Ok(Self {
-                    #fields
-                })
-            }
-            fn stub() -> Self {
-                Default::default()
+            for (object, known) in known_members {
+                let members = data.get(object).and_then(|members| members.as_object());
+
+                for member in members.into_iter().flat_map(|members| members.keys()) {
+                    if known.contains(&member.as_str()) {
+                        continue;
+                    }
+
+                    let error = Error::UnknownField(member.clone()).at_members(&[object, member]);
+                    let error = included_map.resource_error(value, error);
+
+                    if !included_map.collects_errors() {
+                        return Err(error);
+                    }
+
+                    field_errors.push(error);
+                }
             }
         }
+    } else {
+        quote! {}
+    };
+
+    // When collecting errors, a field which failed is missing, but the others are still
+    // deserialized.
+    let mut declare_errors = quote! {};
+    let mut collect_fields = quote! {};
+
+    if !field_names.is_empty() || input_receiver.deny_unknown_fields {
+        declare_errors = quote! { let mut field_errors = Vec::new(); };
+    }
+
+    if !field_names.is_empty() {
+        collect_fields.extend(quote! {
+            let (#(Some(#field_names),)*) = (#(#field_names,)*) else {
+                return Err(Error::multiple(field_errors));
+            };
+        });
+    }
+
+    // Unknown members fail the resource even if every field was deserialized.
+    if input_receiver.deny_unknown_fields {
+        collect_fields.extend(quote! {
+            if !field_errors.is_empty() {
+                return Err(Error::multiple(field_errors));
+            }
+        });
     }
+
+    let from_value_body = quote! {
+        let data = value.as_object().ok_or(Error::InvalidType("Expected an object"))?;
+
+        let resource_type: String = serde_json::from_value(
+            data
+                .get("type")
+                .ok_or_else(|| Error::MissingResourceType)?
+                .clone(),
+        )?;
+
+        if resource_type != #resource_type {
+            let error = Error::ResourceTypeMismatch {
+                expected: #resource_type.to_string(),
+                found: resource_type,
+            };
+
+            return Err(included_map.resource_error(value, error));
+        }
+
+        #declare_errors
+        #deny_unknown_fields
+        #field_initializers
+        #collect_fields
+
+        included_map.visit_resource(value, Self::fields());
+
+        Ok(Self {
+            #fields
+        })
+    };
+
+    (
+        from_value_body,
+        quote! { kind == #resource_type },
+        quote! { &[#(#field_infos),*] },
+    )
 }
@@ -0,0 +1,228 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options, DeserializeError, DeserializeOptions,
+    Document, Error, Holder, JsonApiDeserialize,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    views: u32,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+    #[json_api(relationship = "multiple", resource = "Person")]
+    editors: Vec<&'a Person>,
+    #[json_api(relationship = "multiple", resource = "Tag")]
+    tags: Vec<&'a Tag>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Tag {}
+
+const DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "views": "many"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                },
+                "editors": {
+                    "data": [
+                        { "type": "person", "id": "p-1" },
+                        { "type": "person", "id": "p-2" }
+                    ]
+                },
+                "tags": {
+                    "data": [
+                        { "type": "tag", "id": "t-1" }
+                    ]
+                }
+            }
+        },
+        {
+            "id": "a-2",
+            "type": "article",
+            "attributes": {
+                "title": "Bar",
+                "views": 3
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-2" }
+                },
+                "editors": {
+                    "data": []
+                },
+                "tags": {
+                    "data": []
+                }
+            }
+        }
+    ],
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "age": "old"
+            }
+        },
+        {
+            "type": "person",
+            "id": "p-2",
+            "attributes": {
+                "name": "Jane Doe",
+                "age": 42
+            }
+        },
+        {
+            "type": "tag",
+            "id": "t-1"
+        }
+    ]
+}"#;
+
+#[test]
+fn test_fails_on_first_error_by_default() {
+    let holder = Holder::default();
+    let result: Result<Document<Vec<Article>>, Error> = deserialize_document(DOCUMENT, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected DeserializeError, but got {:?}", result);
+    };
+
+    assert_eq!(error.errors().len(), 1);
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::MissingField("title")
+    ));
+}
+
+#[test]
+fn test_collects_all_errors() {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let result: Result<Document<Vec<Article>>, Error> =
+        deserialize_document_with_options(DOCUMENT, &holder, &options);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let paths = error
+                .errors()
+                .iter()
+                .map(|error| error.path().unwrap().to_string())
+                .collect::<Vec<_>>();
+
+            // The author is referenced twice, but only reported once.
+            assert_eq!(
+                paths,
+                vec![
+                    "data[article:a-1].attributes.title",
+                    "data[article:a-1].attributes.views",
+                    "data[article:a-1].relationships.author -> \
+                     included[person:p-1].attributes.name",
+                    "data[article:a-1].relationships.author -> \
+                     included[person:p-1].attributes.age",
+                ]
+            );
+
+            assert!(matches!(
+                error.errors()[0].kind(),
+                DeserializeError::MissingField("title")
+            ));
+            assert!(matches!(
+                error.errors()[1].kind(),
+                DeserializeError::SerdeError(_)
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_collects_errors_across_resources() {
+    let json = DOCUMENT.replace(r#""title": "Bar","#, "");
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let result: Result<Document<Vec<Article>>, Error> =
+        deserialize_document_with_options(&json, &holder, &options);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert_eq!(error.errors().len(), 5);
+            assert_eq!(
+                error.errors()[4].path().unwrap().to_string(),
+                "data[article:a-2].attributes.title"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_single_error_is_not_aggregated() {
+    let json = DOCUMENT
+        .replace(r#""views": "many""#, r#""title": "Foo", "views": 1"#)
+        .replace(r#""age": "old""#, r#""name": "John Smith", "age": 42"#)
+        .replace(r#""views": 3"#, r#""views": -3"#);
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let result: Result<Document<Vec<Article>>, Error> =
+        deserialize_document_with_options(&json, &holder, &options);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(error.kind(), DeserializeError::SerdeError(_)));
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-2].attributes.views"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_display() {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let result: Result<Document<Vec<Article>>, Error> =
+        deserialize_document_with_options(DOCUMENT, &holder, &options);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(error.to_string().starts_with(
+                "4 errors:\n- data[article:a-1].attributes.title: Missing field\n\
+                 - data[article:a-1]"
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_valid_document() {
+    let json = DOCUMENT
+        .replace(r#""views": "many""#, r#""title": "Foo", "views": 1"#)
+        .replace(r#""age": "old""#, r#""name": "John Smith", "age": 42"#);
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let document: Document<Vec<Article>> =
+        deserialize_document_with_options(&json, &holder, &options).unwrap();
+
+    assert_eq!(document.data[0].editors.len(), 2);
+    assert_eq!(document.data[0].tags.len(), 1);
+}
@@ -0,0 +1,191 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options, DeserializeError, DeserializeOptions,
+    Document, Error, Holder, JsonApiDeserialize, Reference,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(deny_unknown_fields, rename_all = "snake_case")]
+struct Article {
+    id: String,
+    title: String,
+    #[json_api(rename = "viewCount", default)]
+    views: u32,
+    #[json_api(relationship = "single")]
+    main_author: Reference,
+    #[json_api(meta)]
+    meta: Option<serde_json::Value>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(deny_unknown_fields, resource_type = "article")]
+struct Marker {
+    id: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct LenientArticle {
+    id: String,
+    title: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo",
+            "viewCount": 3
+        },
+        "relationships": {
+            "main_author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        },
+        "meta": {
+            "revision": 2
+        }
+    }
+}"#;
+
+#[test]
+fn test_known_members() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.views, 3);
+    assert_eq!(document.data.main_author.id, "p-1");
+}
+
+#[test]
+fn test_unknown_attribute() {
+    let json = DOCUMENT.replace(
+        r#""title": "Foo","#,
+        r#""title": "Foo", "subtitle": "Bar","#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            match error.kind() {
+                DeserializeError::UnknownField(member) => assert_eq!(member, "subtitle"),
+                _ => panic!("Expected UnknownField, but got {:?}", error),
+            }
+
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-1].attributes.subtitle"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_unknown_relationship() {
+    let json = DOCUMENT.replace(
+        r#""main_author": {"#,
+        r#""editor": { "data": null }, "main_author": {"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::UnknownField(member) if member == "editor"
+            ));
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-1].relationships.editor"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_renamed_members() {
+    // With `rename_all = "snake_case"`, the camel-cased name of a field is unknown.
+    let json = DOCUMENT.replace("main_author", "mainAuthor");
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::UnknownField(member) if member == "mainAuthor"
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_struct_without_fields() {
+    let holder = Holder::default();
+    let result: Result<Document<Marker>, Error> = deserialize_document(DOCUMENT, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::UnknownField(member) if member == "title"
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_collect_errors() {
+    let json = DOCUMENT.replace(
+        r#""title": "Foo","#,
+        r#""subtitle": "Bar", "summary": "Baz","#,
+    );
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let result: Result<Document<Article>, Error> =
+        deserialize_document_with_options(&json, &holder, &options);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected DeserializeError, but got {:?}", result);
+    };
+
+    let paths = error
+        .errors()
+        .iter()
+        .map(|error| error.path().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            "data[article:a-1].attributes.subtitle",
+            "data[article:a-1].attributes.summary",
+            "data[article:a-1].attributes.title",
+        ]
+    );
+}
+
+#[test]
+fn test_lenient_by_default() {
+    let json = DOCUMENT.replace(
+        r#""title": "Foo","#,
+        r#""title": "Foo", "subtitle": "Bar","#,
+    );
+    let holder = Holder::default();
+    let document: Document<LenientArticle> = deserialize_document(&json, &holder).unwrap();
+
+    assert_eq!(document.data.title, "Foo");
+}
+
+#[test]
+fn test_rejected_on_enums() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/deny-unknown-fields/*.rs");
+}
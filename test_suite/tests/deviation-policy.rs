@@ -0,0 +1,193 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options,
+    deserialize_shared_document_with_options, DeserializeError, DeserializeOptions,
+    DeviationPolicy, Document, Error, Holder, JsonApiDeserialize, Leniency, SharedDocument,
+    Warning,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+fn deserialize(json: &str, policy: DeviationPolicy) -> Result<Vec<Warning>, Error> {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().deviation_policy(policy);
+    let document: Document<Article> = deserialize_document_with_options(json, &holder, &options)?;
+
+    Ok(document.warnings)
+}
+
+fn numeric_ids() -> String {
+    DOCUMENT
+        .replace(r#""id": "a-1""#, r#""id": 1"#)
+        .replace(r#""p-1""#, "42")
+}
+
+fn duplicate_included() -> String {
+    DOCUMENT.replace(
+        "    ]\n}",
+        r#"    ,
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "Jane Doe"
+            }
+        }
+    ]
+}"#,
+    )
+}
+
+fn data_and_errors() -> String {
+    DOCUMENT.replace(
+        r#""included": ["#,
+        r#""errors": [{ "code": "partial", "title": "Partial result" }],
+        "included": ["#,
+    )
+}
+
+#[test]
+fn test_valid_document() {
+    let warnings = deserialize(DOCUMENT, DeviationPolicy::strict()).unwrap();
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_default_policy() {
+    let holder = Holder::default();
+
+    let result: Result<Document<Article>, Error> = deserialize_document(&numeric_ids(), &holder);
+    assert!(matches!(result, Err(Error::DeserializeError(_))));
+
+    let document: Document<Article> = deserialize_document(&duplicate_included(), &holder).unwrap();
+    assert_eq!(document.data.author.name, "Jane Doe");
+    assert!(document.warnings.is_empty());
+
+    let result: Result<Document<Article>, Error> =
+        deserialize_document(&data_and_errors(), &holder);
+    assert!(matches!(result, Err(Error::DocumentError(_))));
+}
+
+#[test]
+fn test_numeric_ids() {
+    let policy = DeviationPolicy::new().numeric_ids(Leniency::Warn);
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().deviation_policy(policy);
+    let document: Document<Article> =
+        deserialize_document_with_options(&numeric_ids(), &holder, &options).unwrap();
+
+    assert_eq!(document.data.id, "1");
+    assert_eq!(document.data.author.id, "42");
+
+    // The included resource and the identifier pointing at it only result in one warning.
+    let warnings = document
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::NumericId { kind, id } => (kind.as_str(), id.as_str()),
+            _ => panic!("Expected NumericId, but got {:?}", warning),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(warnings, vec![("article", "1"), ("person", "42")]);
+
+    let policy = DeviationPolicy::new().numeric_ids(Leniency::Accept);
+    assert!(deserialize(&numeric_ids(), policy).unwrap().is_empty());
+}
+
+#[test]
+fn test_duplicate_included() {
+    let policy = DeviationPolicy::new().duplicate_included(Leniency::Warn);
+    let warnings = deserialize(&duplicate_included(), policy).unwrap();
+
+    assert!(matches!(
+        warnings.as_slice(),
+        [Warning::DuplicateIncluded { kind, id }] if kind == "person" && id == "p-1"
+    ));
+
+    let policy = DeviationPolicy::new().duplicate_included(Leniency::Reject);
+    let Err(Error::DeserializeError(error)) = deserialize(&duplicate_included(), policy) else {
+        panic!("Expected DeserializeError");
+    };
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::DuplicateResource { kind, id } if kind == "person" && id == "p-1"
+    ));
+}
+
+#[test]
+fn test_data_and_errors() {
+    let policy = DeviationPolicy::new().data_and_errors(Leniency::Warn);
+    let warnings = deserialize(&data_and_errors(), policy).unwrap();
+
+    let [Warning::DataAndErrors(errors)] = warnings.as_slice() else {
+        panic!("Expected DataAndErrors, but got {:?}", warnings);
+    };
+    assert_eq!(errors[0].code.as_deref(), Some("partial"));
+
+    let policy = DeviationPolicy::new().data_and_errors(Leniency::Accept);
+    assert!(deserialize(&data_and_errors(), policy).unwrap().is_empty());
+}
+
+#[test]
+fn test_error_document_takes_precedence() {
+    let json = duplicate_included().replace(r#""data": {"#, r#""errors": [{}], "meta": {"#);
+    let result = deserialize(&json, DeviationPolicy::strict());
+
+    assert!(matches!(result, Err(Error::DocumentError(_))));
+}
+
+#[test]
+fn test_lenient_shared_document() {
+    #[derive(Debug, JsonApiDeserialize)]
+    #[json_api(resource_type = "article")]
+    struct SharedArticle {
+        id: String,
+    }
+
+    let json = numeric_ids().replace(r#""included": ["#, r#""errors": [{}], "included": ["#);
+    let options = DeserializeOptions::new().deviation_policy(DeviationPolicy::lenient());
+    let document: SharedDocument<SharedArticle> =
+        deserialize_shared_document_with_options(&json, &options).unwrap();
+
+    assert_eq!(document.data.id, "1");
+    assert_eq!(document.warnings.len(), 3);
+}
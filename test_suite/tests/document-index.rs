@@ -0,0 +1,173 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_owned_document, deserialize_sync_document, DeserializeError,
+    Document, Holder, JsonApiDeserialize, OwnedDocument, Reference, SyncHolder,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single")]
+    author: Reference,
+    #[json_api(relationship = "multiple")]
+    comments: Vec<Reference>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment<'a> {
+    content: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "comments": {
+                "data": [
+                    { "type": "comment", "id": "c-1" },
+                    { "type": "comment", "id": "c-2" }
+                ]
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-1",
+            "attributes": {
+                "content": "Lorem Ipsum"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-2" }
+                }
+            }
+        },
+        {
+            "type": "person",
+            "id": "p-2",
+            "attributes": {
+                "name": "Jane Doe"
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-2",
+            "attributes": {
+                "content": "Dolor sit amet"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_resolve() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let author = document.resolve::<Person>(&document.data.author).unwrap();
+    assert_eq!(author.name, "John Smith");
+    assert!(std::ptr::eq(
+        author,
+        document.resolve::<Person>(&document.data.author).unwrap()
+    ));
+
+    let comment = document
+        .resolve::<Comment>(&document.data.comments[1])
+        .unwrap();
+    assert_eq!(comment.content, "Dolor sit amet");
+    assert!(std::ptr::eq(comment.author, author));
+}
+
+#[test]
+fn test_resolve_missing_resource() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let reference = Reference::new("person", "p-3");
+
+    match document.resolve::<Person>(&reference) {
+        Err(DeserializeError::MissingResource { kind, id }) => {
+            assert_eq!(kind, "person");
+            assert_eq!(id, "p-3");
+        }
+        result => panic!("Expected MissingResource, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_included_of() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let people = document
+        .included_of::<Person>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(people.len(), 2);
+    assert_eq!(people[0].name, "John Smith");
+    assert_eq!(people[1].name, "Jane Doe");
+
+    let comments = document
+        .included_of::<Comment>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(comments.len(), 2);
+    assert!(std::ptr::eq(comments[0].author, people[1]));
+}
+
+#[test]
+fn test_find() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let person = document.find("person", "p-2").unwrap();
+    assert_eq!(person["attributes"]["name"], "Jane Doe");
+
+    let article = document.find("article", "a-1").unwrap();
+    assert_eq!(article["attributes"]["title"], "Foo");
+
+    assert!(document.find("person", "p-3").is_none());
+}
+
+#[test]
+fn test_owned_document() {
+    let document: OwnedDocument<Article> = deserialize_owned_document(DOCUMENT).unwrap();
+    let author = document.resolve::<Person>(&document.data().author).unwrap();
+    assert_eq!(author.name, "John Smith");
+
+    let document: OwnedDocument<Article, SyncHolder> = deserialize_sync_document(DOCUMENT).unwrap();
+    let comment = document
+        .resolve::<Comment<'static>>(&document.data().comments[0])
+        .unwrap();
+    assert_eq!(comment.author.name, "Jane Doe");
+    assert_eq!(document.included_of::<Person>().count(), 2);
+}
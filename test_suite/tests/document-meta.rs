@@ -0,0 +1,96 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_meta, deserialize_shared_document,
+    DeserializeError, Document, Error, Holder, JsonApiDeserialize, SharedDocument,
+};
+use serde::Deserialize;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageMeta {
+    total_count: u32,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            }
+        }
+    ],
+    "meta": {
+        "totalCount": 42
+    }
+}"#;
+
+#[test]
+fn test_typed_meta() {
+    let holder = Holder::default();
+    let document: Document<Vec<Article>, PageMeta> =
+        deserialize_document_with_meta(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.len(), 1);
+    assert_eq!(document.meta.unwrap().total_count, 42);
+}
+
+#[test]
+fn test_default_meta() {
+    let holder = Holder::default();
+    let document: Document<Vec<Article>> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.meta.unwrap()["totalCount"], 42);
+}
+
+#[test]
+fn test_turbofish() {
+    let holder = Holder::default();
+    let document = deserialize_document::<Vec<Article>>(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.meta.unwrap()["totalCount"], 42);
+}
+
+#[test]
+fn test_missing_meta() {
+    let json = r#"{
+        "data": []
+    }"#;
+    let holder = Holder::default();
+    let document: Document<Vec<Article>, PageMeta> =
+        deserialize_document_with_meta(json, &holder).unwrap();
+
+    assert!(document.meta.is_none());
+}
+
+#[test]
+fn test_mismatching_meta() {
+    let json = DOCUMENT.replace("42", r#""many""#);
+    let holder = Holder::default();
+    let result: Result<Document<Vec<Article>, PageMeta>, Error> =
+        deserialize_document_with_meta(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(DeserializeError::InvalidMeta(error))) => {
+            assert!(error.to_string().contains("invalid type"));
+        }
+        _ => panic!("Expected InvalidMeta, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_shared_document() {
+    let document: SharedDocument<Vec<Article>, PageMeta> =
+        deserialize_shared_document(DOCUMENT).unwrap();
+
+    assert_eq!(document.data[0].title, "Foo");
+    assert_eq!(document.meta.unwrap().total_count, 42);
+}
@@ -0,0 +1,290 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options,
+    deserialize_shared_document_with_options, DeserializeOptions, Document, DriftReport, Holder,
+    JsonApiDeserialize, Lazy, Reference, SharedDocument, UnmappedMember,
+};
+use std::sync::Arc;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(default)]
+    view_count: u32,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+    #[json_api(relationship = "multiple")]
+    tags: Vec<Reference>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct SharedArticle {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "SharedPerson")]
+    author: Arc<SharedPerson>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "person")]
+struct SharedPerson {
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo",
+                "subtitle": "Bar"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                },
+                "tags": {
+                    "data": [{ "type": "tag", "id": "t-1" }]
+                },
+                "comments": {
+                    "data": [{ "type": "comment", "id": "c-1" }]
+                }
+            }
+        },
+        {
+            "id": "a-2",
+            "type": "article",
+            "attributes": {
+                "title": "Baz",
+                "viewCount": 3,
+                "subtitle": "Qux"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                },
+                "tags": {
+                    "data": []
+                }
+            }
+        }
+    ],
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith",
+                "age": 42
+            }
+        },
+        {
+            "type": "person",
+            "id": "p-2",
+            "attributes": {
+                "name": "Jane Doe"
+            }
+        },
+        {
+            "type": "tag",
+            "id": "t-1",
+            "attributes": {
+                "label": "News"
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-1",
+            "attributes": {
+                "body": "First"
+            }
+        }
+    ]
+}"#;
+
+fn member(kind: &str, member: &str) -> UnmappedMember {
+    UnmappedMember {
+        kind: kind.to_string(),
+        member: member.to_string(),
+    }
+}
+
+fn drift_report(json: &str) -> DriftReport {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().report_drift(true);
+    let document: Document<Vec<Article>> =
+        deserialize_document_with_options(json, &holder, &options).unwrap();
+
+    document.drift.unwrap()
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let holder = Holder::default();
+    let document: Document<Vec<Article>> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(document.drift.is_none());
+}
+
+#[test]
+fn test_unused_included() {
+    let report = drift_report(DOCUMENT);
+
+    let unused = report
+        .unused_included
+        .iter()
+        .map(|reference| (reference.kind.as_str(), reference.identifier()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        unused,
+        vec![("comment", "c-1"), ("person", "p-2"), ("tag", "t-1")]
+    );
+}
+
+#[test]
+fn test_unmapped_members() {
+    let report = drift_report(DOCUMENT);
+
+    // Renamed fields count as mapped, and each member is only reported once per type.
+    assert_eq!(
+        report.unmapped_attributes,
+        vec![member("article", "subtitle"), member("person", "age")]
+    );
+    assert_eq!(
+        report.unmapped_relationships,
+        vec![member("article", "comments")]
+    );
+}
+
+#[test]
+fn test_ignored_relationships() {
+    let report = drift_report(DOCUMENT);
+
+    // The tags are mapped, but only as references, so the included tag is never reached.
+    assert_eq!(
+        report.ignored_relationships,
+        vec![member("article", "tags")]
+    );
+}
+
+#[test]
+fn test_no_drift() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo",
+                "viewCount": 1
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                },
+                "tags": {
+                    "data": [{ "type": "tag", "id": "t-1" }]
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith"
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().report_drift(true);
+    let document: Document<Article> =
+        deserialize_document_with_options(json, &holder, &options).unwrap();
+    let report = document.drift.unwrap();
+
+    assert!(report.is_empty(), "unexpected drift:\n{}", report);
+}
+
+#[test]
+fn test_display() {
+    let report = drift_report(DOCUMENT);
+
+    assert_eq!(
+        report.to_string(),
+        "unused included resource comment:c-1\n\
+         unused included resource person:p-2\n\
+         unused included resource tag:t-1\n\
+         unmapped attribute article.subtitle\n\
+         unmapped attribute person.age\n\
+         unmapped relationship article.comments\n\
+         ignored relationship article.tags\n"
+    );
+}
+
+#[test]
+fn test_shared_document() {
+    let options = DeserializeOptions::new().report_drift(true);
+    let document: SharedDocument<Vec<SharedArticle>> =
+        deserialize_shared_document_with_options(DOCUMENT, &options).unwrap();
+    let report = document.drift.unwrap();
+
+    assert_eq!(report.unused_included.len(), 3);
+    assert_eq!(
+        report.unmapped_relationships,
+        vec![member("article", "comments"), member("article", "tags")]
+    );
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct LazyArticle<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: Lazy<'a, Person>,
+}
+
+#[test]
+fn test_lazy_relationships_are_used() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith"
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().report_drift(true);
+    let document: Document<LazyArticle> =
+        deserialize_document_with_options(json, &holder, &options).unwrap();
+    let report = document.drift.unwrap();
+
+    assert!(report.is_empty(), "unexpected drift:\n{}", report);
+    assert_eq!(document.data.author.get().unwrap().name, "John Smith");
+}
@@ -0,0 +1,200 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize, Location,
+    PathSegment,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person<'a>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person<'a> {
+    id: String,
+    name: String,
+    age: u32,
+    #[json_api(relationship = "optional", resource = "Company")]
+    employer: Option<&'a Company>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Company {
+    legal_name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith",
+                "age": 42
+            },
+            "relationships": {
+                "employer": {
+                    "data": { "type": "company", "id": "c-1" }
+                }
+            }
+        },
+        {
+            "type": "company",
+            "id": "c-1",
+            "attributes": {
+                "legalName": "ACME Inc."
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_valid_document() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(
+        document.data.author.employer.unwrap().legal_name,
+        "ACME Inc."
+    );
+}
+
+#[test]
+fn test_missing_attribute_in_primary_data() {
+    let json = DOCUMENT.replace(r#""title": "Foo""#, r#""subtitle": "Foo""#);
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::MissingField("title")
+            ));
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-1].attributes.title"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_message_is_not_repeated_by_source() {
+    let json = DOCUMENT.replace(r#""title": "Foo""#, r#""subtitle": "Foo""#);
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected DeserializeError, but got {:?}", result);
+    };
+
+    // Reporters print the source chain after the message, which already includes the error.
+    assert!(error.to_string().ends_with(&error.kind().to_string()));
+    assert!(std::error::Error::source(&error).is_none());
+}
+
+#[test]
+fn test_included_resource() {
+    let json = DOCUMENT.replace(r#""name": "John Smith","#, "");
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-1].relationships.author -> included[person:p-1].attributes.name"
+            );
+
+            let path = error.path().unwrap();
+            assert_eq!(
+                path.resources().collect::<Vec<_>>(),
+                vec![("article", "a-1"), ("person", "p-1")]
+            );
+            assert_eq!(path.member(), Some("name"));
+            assert_eq!(
+                path.segments[3],
+                PathSegment::Resource {
+                    location: Location::Included,
+                    kind: "person".to_string(),
+                    id: "p-1".to_string(),
+                }
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_serde_error() {
+    let json = DOCUMENT.replace("42", r#""forty-two""#);
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(error.kind(), DeserializeError::SerdeError(_)));
+            assert!(error.to_string().starts_with(
+                "data[article:a-1].relationships.author -> included[person:p-1].attributes.age: "
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_missing_resource() {
+    let json = DOCUMENT.replace(r#""id": "c-1","#, r#""id": "c-2","#);
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            match error.kind() {
+                DeserializeError::MissingResource { kind, id } => {
+                    assert_eq!(kind, "company");
+                    assert_eq!(id, "c-1");
+                }
+                _ => panic!("Expected MissingResource, but got {:?}", error),
+            }
+
+            assert_eq!(
+                error.path().unwrap().to_string(),
+                "data[article:a-1].relationships.author -> \
+                 included[person:p-1].relationships.employer"
+            );
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_display() {
+    let holder = Holder::default();
+    let json = DOCUMENT.replace(r#""legalName": "ACME Inc.""#, "");
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "data[article:a-1].relationships.author -> included[person:p-1].relationships.employer \
+         -> included[company:c-1].attributes.legalName: Missing field"
+    );
+}
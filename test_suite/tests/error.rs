@@ -1,6 +1,9 @@
 #![allow(unused)]
 
-use jsonapi_deserialize::{deserialize_document, Document, Error, JsonApiDeserialize, Holder};
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_error_document, DeserializeError, Document, Error, Holder,
+    JsonApiDeserialize,
+};
 
 #[derive(Debug, JsonApiDeserialize, Default)]
 struct Foo {
@@ -16,7 +19,7 @@ fn test_document_errors() {
                 "status": "404"
             }]
         }"#,
-        &holder
+        &holder,
     );
 
     let errors = if let Err(Error::DocumentError(errors)) = result {
@@ -25,5 +28,76 @@ fn test_document_errors() {
         panic!("Expected DocumentError, but got {:?}", result);
     };
 
-    assert_eq!(errors.get(0).unwrap().status, Some("404".to_string()));
+    assert_eq!(errors.first().unwrap().status, Some("404".to_string()));
+}
+
+const ERROR_DOCUMENT: &str = r#"{
+    "errors": [{
+        "status": "422",
+        "code": "invalid-title",
+        "source": { "pointer": "/data/attributes/title" }
+    }],
+    "meta": {
+        "requestId": "r-123"
+    },
+    "links": {
+        "describedby": "https://example.com/errors"
+    },
+    "jsonapi": {
+        "version": "1.1"
+    }
+}"#;
+
+#[test]
+fn test_error_document_members() {
+    let holder = Holder::default();
+    let result: Result<Document<Foo>, Error> = deserialize_document(ERROR_DOCUMENT, &holder);
+
+    let document = if let Err(Error::DocumentError(document)) = result {
+        document
+    } else {
+        panic!("Expected DocumentError, but got {:?}", result);
+    };
+
+    assert_eq!(document.meta.as_ref().unwrap()["requestId"], "r-123");
+    assert_eq!(
+        document
+            .links
+            .as_ref()
+            .unwrap()
+            .described_by
+            .as_ref()
+            .unwrap()
+            .href,
+        "https://example.com/errors"
+    );
+    assert_eq!(
+        document.jsonapi.as_ref().unwrap().version.as_deref(),
+        Some("1.1")
+    );
+    assert_eq!(document[0].code.as_deref(), Some("invalid-title"));
+}
+
+#[test]
+fn test_deserialize_error_document() {
+    let document = deserialize_error_document(ERROR_DOCUMENT).unwrap();
+
+    assert_eq!(document.errors.len(), 1);
+    assert_eq!(
+        document.errors[0]
+            .source
+            .as_ref()
+            .unwrap()
+            .pointer
+            .as_deref(),
+        Some("/data/attributes/title")
+    );
+    assert_eq!(document.meta.unwrap()["requestId"], "r-123");
+}
+
+#[test]
+fn test_deserialize_error_document_without_errors() {
+    let result = deserialize_error_document(r#"{ "data": null }"#);
+
+    assert!(matches!(result, Err(DeserializeError::IncompleteDocument)));
 }
@@ -0,0 +1,125 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(ext = "version:id")]
+    version: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+    #[json_api(ext = "version:id")]
+    version: Option<String>,
+}
+
+const DOCUMENT: &str = r#"{
+    "version:latest": "v-3",
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "version:id": "v-2",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_document_members() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.extensions.len(), 1);
+    assert_eq!(document.extensions["version:latest"], "v-3");
+}
+
+#[test]
+fn test_resource_members() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.version, "v-2");
+    assert!(document.data.author.version.is_none());
+}
+
+#[test]
+fn test_missing_resource_member() {
+    let json = DOCUMENT.replace(r#""version:id": "v-2","#, "");
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected MissingField, but got {:?}", result);
+    };
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::MissingField("version")
+    ));
+    assert_eq!(
+        error.path().unwrap().to_string(),
+        "data[article:a-1].version:id"
+    );
+}
+
+#[test]
+fn test_invalid_namespace() {
+    for (from, to) in [
+        ("version:latest", "ver-sion:latest"),
+        ("version:id", ":id"),
+        ("version:id", "version:-id"),
+        ("version:latest", "ver_sion:latest"),
+        ("version:latest", "version:"),
+    ] {
+        let json = DOCUMENT.replace(from, to);
+        let holder = Holder::default();
+        let result: Result<Document<Vec<Person>>, Error> = deserialize_document(&json, &holder);
+
+        match result {
+            Err(Error::DeserializeError(DeserializeError::InvalidExtensionMember(name))) => {
+                assert_eq!(name, to)
+            }
+            _ => panic!("Expected InvalidExtensionMember, but got {:?}", result),
+        }
+    }
+}
+
+#[test]
+fn test_naming_rules() {
+    for name in ["v2:latest", "atomic:operations", "version:latest-id"] {
+        let json = DOCUMENT.replace("version:latest", name);
+        let holder = Holder::default();
+        let document: Document<Article> = deserialize_document(&json, &holder).unwrap();
+
+        assert_eq!(document.extensions[name], "v-3");
+    }
+}
+
+#[test]
+fn test_invalid_namespace_in_derive() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/extension/*.rs");
+}
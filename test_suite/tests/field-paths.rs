@@ -0,0 +1,130 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_error_document, Field, FieldKind, FieldPath, IgnoreUnknown, JsonApiDeserialize,
+    Reference,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Address {
+    street: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    first_name: String,
+    #[json_api(rename = "surname")]
+    last_name: String,
+    address: Address,
+    #[json_api(relationship = "single")]
+    employer: Reference,
+    #[json_api(meta)]
+    meta: Option<serde_json::Value>,
+    #[json_api(ext = "version:id")]
+    version: Option<String>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(rename_all = "snake_case")]
+struct Company {
+    legal_name: String,
+}
+
+#[test]
+fn test_attribute() {
+    let path = FieldPath::resolve::<Person>("/data/attributes/firstName").unwrap();
+
+    assert_eq!(path.index, None);
+    assert_eq!(
+        path.field,
+        Field {
+            name: "first_name",
+            member: "firstName",
+            kind: FieldKind::Attribute,
+        }
+    );
+    assert!(path.rest.is_empty());
+}
+
+#[test]
+fn test_renamed_attribute() {
+    let path = FieldPath::resolve::<Person>("/data/attributes/surname").unwrap();
+    assert_eq!(path.field.name, "last_name");
+
+    let path = FieldPath::resolve::<Company>("/data/attributes/legal_name").unwrap();
+    assert_eq!(path.field.name, "legal_name");
+
+    assert!(FieldPath::resolve::<Person>("/data/attributes/lastName").is_none());
+}
+
+#[test]
+fn test_nested_attribute() {
+    let path = FieldPath::resolve::<Person>("/data/attributes/address/street").unwrap();
+
+    assert_eq!(path.field.name, "address");
+    assert_eq!(path.rest, vec!["street".to_string()]);
+}
+
+#[test]
+fn test_collection() {
+    let path = FieldPath::resolve::<Vec<Person>>("/data/2/attributes/firstName").unwrap();
+
+    assert_eq!(path.index, Some(2));
+    assert_eq!(path.field.name, "first_name");
+
+    let path = FieldPath::resolve::<IgnoreUnknown<Person>>("/data/2/attributes/firstName").unwrap();
+
+    assert_eq!(path.index, Some(2));
+    assert_eq!(path.field.name, "first_name");
+}
+
+#[test]
+fn test_other_members() {
+    let path = FieldPath::resolve::<Person>("/data/relationships/employer/data").unwrap();
+    assert_eq!(path.field.name, "employer");
+    assert_eq!(path.field.kind, FieldKind::Relationship);
+
+    let path = FieldPath::resolve::<Person>("/data/id").unwrap();
+    assert_eq!(path.field.kind, FieldKind::Id);
+
+    let path = FieldPath::resolve::<Person>("/data/meta/createdBy").unwrap();
+    assert_eq!(path.field.name, "meta");
+    assert_eq!(path.rest, vec!["createdBy".to_string()]);
+
+    let path = FieldPath::resolve::<Person>("/data/version:id").unwrap();
+    assert_eq!(path.field.name, "version");
+}
+
+#[test]
+fn test_unknown_pointer() {
+    assert!(FieldPath::resolve::<Person>("/data/attributes/age").is_none());
+    assert!(FieldPath::resolve::<Person>("/included/0/attributes/firstName").is_none());
+    assert!(FieldPath::resolve::<Person>("").is_none());
+}
+
+#[test]
+fn test_document_error() {
+    let document = deserialize_error_document(
+        r#"{
+            "errors": [
+                {
+                    "status": "422",
+                    "source": { "pointer": "/data/attributes/firstName" }
+                },
+                {
+                    "status": "400",
+                    "source": { "parameter": "include" }
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        document[0].field_path::<Person>().unwrap().field.name,
+        "first_name"
+    );
+    assert!(document[1].field_path::<Person>().is_none());
+}
@@ -0,0 +1,111 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, AnyOf2, AnyOf3, DeserializeError, Document, Error, Holder, IgnoreUnknown,
+    JsonApiDeserialize,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+    title: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Video {
+    id: String,
+    url: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            }
+        },
+        {
+            "id": "v-1",
+            "type": "video",
+            "attributes": {
+                "url": "https://example.com/v-1.mp4"
+            }
+        },
+        {
+            "id": "p-1",
+            "type": "person",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_mixed_collection() {
+    let holder = Holder::default();
+    let document: Document<Vec<AnyOf3<Article, Video, Person>>> =
+        deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.len(), 3);
+    assert!(matches!(&document.data[0], AnyOf3::A(article) if article.title == "Foo"));
+    assert!(matches!(&document.data[1], AnyOf3::B(video) if video.id == "v-1"));
+    assert!(matches!(&document.data[2], AnyOf3::C(person) if person.name == "John Smith"));
+}
+
+#[test]
+fn test_unknown_type_fails() {
+    let holder = Holder::default();
+    let result: Result<Document<Vec<AnyOf2<Article, Video>>>, Error> =
+        deserialize_document(DOCUMENT, &holder);
+
+    match result {
+        Err(Error::DeserializeError(DeserializeError::ResourceTypeMismatch { found, .. })) => {
+            assert_eq!(found, "person")
+        }
+        _ => panic!("Expected ResourceTypeMismatch, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_ignore_unknown_types() {
+    let holder = Holder::default();
+    let document: Document<IgnoreUnknown<AnyOf2<Article, Video>>> =
+        deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.len(), 2);
+    assert!(matches!(&document.data[0], AnyOf2::A(article) if article.id == "a-1"));
+    assert!(matches!(&document.data[1], AnyOf2::B(video) if video.id == "v-1"));
+}
+
+#[test]
+fn test_ignore_unknown_still_reports_invalid_resources() {
+    let holder = Holder::default();
+    let result: Result<Document<IgnoreUnknown<Article>>, Error> = deserialize_document(
+        r#"{
+            "data": [
+                { "id": "p-1", "type": "person" },
+                { "id": "a-1", "type": "article", "attributes": {} }
+            ]
+        }"#,
+        &holder,
+    );
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::MissingField("title")
+            ))
+        }
+        _ => panic!("Expected MissingField, but got {:?}", result),
+    }
+}
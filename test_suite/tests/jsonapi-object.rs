@@ -0,0 +1,112 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options, DeserializeError, DeserializeOptions,
+    Document, Error, Holder, JsonApiDeserialize,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+    title: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "jsonapi": {
+        "version": "1.1",
+        "ext": ["https://jsonapi.org/ext/atomic"],
+        "profile": ["http://example.com/profiles/flexible-pagination"],
+        "meta": {
+            "server": "example"
+        }
+    },
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        }
+    }
+}"#;
+
+#[test]
+fn test_jsonapi_object() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let jsonapi = document.jsonapi.unwrap();
+
+    assert_eq!(jsonapi.version.as_deref(), Some("1.1"));
+    assert_eq!(jsonapi.ext.len(), 1);
+    assert_eq!(jsonapi.ext[0], "https://jsonapi.org/ext/atomic");
+    assert_eq!(
+        jsonapi.profile[0],
+        "http://example.com/profiles/flexible-pagination"
+    );
+    assert_eq!(jsonapi.meta.unwrap()["server"], "example");
+}
+
+#[test]
+fn test_missing_jsonapi_object() {
+    let json = r#"{
+        "jsonapi": {},
+        "data": []
+    }"#;
+    let holder = Holder::default();
+    let document: Document<Vec<Article>> = deserialize_document(json, &holder).unwrap();
+    let jsonapi = document.jsonapi.unwrap();
+
+    assert!(jsonapi.version.is_none());
+    assert!(jsonapi.ext.is_empty());
+    assert!(jsonapi.profile.is_empty());
+}
+
+#[test]
+fn test_error_document() {
+    let json = r#"{
+        "jsonapi": {
+            "version": "1.1"
+        },
+        "errors": [{
+            "status": "500"
+        }]
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DocumentError(document)) => {
+            assert_eq!(
+                document.jsonapi.as_ref().unwrap().version.as_deref(),
+                Some("1.1")
+            );
+            assert_eq!(document.errors[0].status.as_deref(), Some("500"));
+        }
+        _ => panic!("Expected DocumentError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_supported_extensions() {
+    let holder = Holder::default();
+    let options =
+        DeserializeOptions::new().supported_extensions(["https://jsonapi.org/ext/atomic"]);
+    let document: Document<Article> =
+        deserialize_document_with_options(DOCUMENT, &holder, &options).unwrap();
+
+    assert_eq!(document.data.title, "Foo");
+}
+
+#[test]
+fn test_unsupported_extension() {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().supported_extensions(Vec::<String>::new());
+    let result: Result<Document<Article>, Error> =
+        deserialize_document_with_options(DOCUMENT, &holder, &options);
+
+    match result {
+        Err(Error::DeserializeError(DeserializeError::UnsupportedExtension(extension))) => {
+            assert_eq!(extension, "https://jsonapi.org/ext/atomic");
+        }
+        _ => panic!("Expected UnsupportedExtension, but got {:?}", result),
+    }
+}
@@ -0,0 +1,154 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_owned_document, DeserializeError, Document, Error, Holder,
+    JsonApiDeserialize, Lazy, OwnedDocument,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: Lazy<'a, Person>,
+    #[json_api(relationship = "optional", resource = "Person")]
+    reviewer: Option<Lazy<'a, Person>>,
+    #[json_api(relationship = "multiple", resource = "Comment")]
+    comments: Vec<Lazy<'a, Comment<'a>>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment<'a> {
+    content: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "reviewer": {
+                "data": null
+            },
+            "comments": {
+                "data": [
+                    { "type": "comment", "id": "c-1" },
+                    { "type": "comment", "id": "c-2" }
+                ]
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-1",
+            "attributes": {
+                "content": "Lorem Ipsum"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-2",
+            "attributes": {},
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_deserialized_on_first_access() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(!document.data.author.is_loaded());
+    assert_eq!(document.data.author.reference().id.as_deref(), Some("p-1"));
+    assert!(document.data.reviewer.is_none());
+
+    assert_eq!(document.data.author.get().unwrap().name, "John Smith");
+    assert!(document.data.author.is_loaded());
+
+    let comment = &document.data.comments[0];
+    assert!(!comment.is_loaded());
+    assert_eq!(comment.get().unwrap().content, "Lorem Ipsum");
+    assert!(std::ptr::eq(
+        comment.get().unwrap().author,
+        document.data.author.get().unwrap()
+    ));
+}
+
+#[test]
+fn test_invalid_resource_fails_on_access() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let error = document.data.comments[1].get().unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::MissingField("content")
+    ));
+    assert_eq!(
+        error.path().unwrap().to_string(),
+        "included[comment:c-2].attributes.content"
+    );
+}
+
+#[test]
+fn test_missing_resource_fails_eagerly() {
+    let json = DOCUMENT.replace(r#""id": "p-1","#, r#""id": "p-2","#);
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected MissingResource, but got {:?}", result);
+    };
+
+    match error.kind() {
+        DeserializeError::MissingResource { kind, id } => {
+            assert_eq!(kind, "person");
+            assert_eq!(id, "p-1");
+        }
+        _ => panic!("Expected MissingResource, but got {:?}", error),
+    }
+}
+
+#[test]
+fn test_owned_document() {
+    let document: OwnedDocument<Article<'static>> = deserialize_owned_document(DOCUMENT).unwrap();
+
+    assert!(!document.data().author.is_loaded());
+    assert_eq!(document.data().author.get().unwrap().name, "John Smith");
+    assert!(std::ptr::eq(
+        document.data().comments[0].get().unwrap().author,
+        document.data().author.get().unwrap()
+    ));
+}
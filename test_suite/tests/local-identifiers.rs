@@ -0,0 +1,174 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize,
+    LocalReference,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: Option<String>,
+    #[json_api(lid)]
+    lid: Option<String>,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+    #[json_api(relationship = "multiple")]
+    tags: Vec<LocalReference>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: Option<String>,
+    #[json_api(lid)]
+    lid: Option<String>,
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "lid": "local-article",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "lid": "local-person" }
+            },
+            "tags": {
+                "data": [
+                    { "type": "tag", "id": "t-1" },
+                    { "type": "tag", "lid": "local-tag" }
+                ]
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "lid": "local-person",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_resolve_by_lid() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(document.data.id.is_none());
+    assert_eq!(document.data.lid.as_deref(), Some("local-article"));
+    assert_eq!(document.data.author.name, "John Smith");
+    assert_eq!(document.data.author.lid.as_deref(), Some("local-person"));
+    assert!(std::ptr::eq(
+        document.data.author,
+        document
+            .resolve::<Person>(&LocalReference::local("person", "local-person"))
+            .unwrap()
+    ));
+}
+
+#[test]
+fn test_reference_lid() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let tags = &document.data.tags;
+
+    assert_eq!(tags[0].id.as_deref(), Some("t-1"));
+    assert!(tags[0].lid.is_none());
+    assert!(tags[1].id.is_none());
+    assert_eq!(tags[1].lid.as_deref(), Some("local-tag"));
+    assert_eq!(tags[1].identifier(), "local-tag");
+}
+
+#[test]
+fn test_resource_with_id_and_lid() {
+    let json = DOCUMENT.replace(
+        r#""lid": "local-person",
+            "attributes""#,
+        r#""id": "p-1",
+            "lid": "local-person",
+            "attributes""#,
+    );
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(&json, &holder).unwrap();
+
+    let author = document.data.author;
+    assert_eq!(author.id.as_deref(), Some("p-1"));
+    assert!(std::ptr::eq(
+        author,
+        document
+            .resolve::<Person>(&LocalReference::new("person", "p-1"))
+            .unwrap()
+    ));
+}
+
+#[test]
+fn test_missing_identifier() {
+    let json = DOCUMENT.replace(
+        r#"{ "type": "tag", "lid": "local-tag" }"#,
+        r#"{ "type": "tag" }"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_inconsistent_lid() {
+    let json = DOCUMENT.replace(
+        r#""included": ["#,
+        r#""included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "lid": "local-person",
+            "attributes": {
+                "name": "Jane Doe"
+            }
+        },"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(DeserializeError::InconsistentLid { kind, lid })) => {
+            assert_eq!(kind, "person");
+            assert_eq!(lid, "local-person");
+        }
+        _ => panic!("Expected InconsistentLid, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_reference_with_mismatching_lid() {
+    let json = DOCUMENT
+        .replace(
+            r#""lid": "local-person",
+            "attributes""#,
+            r#""id": "p-1",
+            "lid": "local-person",
+            "attributes""#,
+        )
+        .replace(
+            r#"{ "type": "person", "lid": "local-person" }"#,
+            r#"{ "type": "person", "id": "p-2", "lid": "local-person" }"#,
+        );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::InconsistentLid { .. }
+            ))
+        }
+        _ => panic!("Expected InconsistentLid, but got {:?}", result),
+    }
+}
@@ -0,0 +1,214 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_document_with_options, DeserializeError, DeserializeOptions,
+    Document, Error, Holder, JsonApiDeserialize, Lossy, Relationship,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "multiple", resource = "Person", lossy)]
+    editors: Lossy<Vec<&'a Person>>,
+    #[json_api(
+        relationship = "multiple",
+        resource = "Person",
+        relationship_object,
+        lossy
+    )]
+    reviewers: Relationship<Lossy<Vec<&'a Person>>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    id: String,
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "editors": {
+                    "data": [
+                        { "type": "person", "id": "p-1" },
+                        { "type": "person", "id": "p-2" },
+                        { "type": "person", "id": "p-3" }
+                    ]
+                },
+                "reviewers": {
+                    "data": [
+                        { "type": "person", "id": "p-1" }
+                    ]
+                }
+            }
+        },
+        {
+            "id": "a-2",
+            "type": "article",
+            "attributes": {},
+            "relationships": {
+                "editors": {
+                    "data": []
+                },
+                "reviewers": {
+                    "data": []
+                }
+            }
+        },
+        {
+            "id": "a-3",
+            "type": "article",
+            "attributes": {
+                "title": "Baz"
+            },
+            "relationships": {
+                "editors": {
+                    "data": []
+                },
+                "reviewers": {
+                    "data": [
+                        { "type": "person", "id": "p-2" },
+                        { "type": "person", "id": "p-4" }
+                    ]
+                }
+            }
+        }
+    ],
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        },
+        {
+            "type": "person",
+            "id": "p-2",
+            "attributes": {}
+        },
+        {
+            "type": "person",
+            "id": "p-3",
+            "attributes": {
+                "name": "Jane Doe"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_primary_data() {
+    let holder = Holder::default();
+    let document: Document<Lossy<Vec<Article>>> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let ids = document
+        .data
+        .iter()
+        .map(|article| article.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["a-1", "a-3"]);
+
+    assert!(!document.data.is_complete());
+    assert_eq!(document.data.errors().len(), 1);
+
+    let error = &document.data.errors()[0];
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::MissingField("title")
+    ));
+    assert_eq!(
+        error.path().unwrap().to_string(),
+        "data[article:a-2].attributes.title"
+    );
+}
+
+#[test]
+fn test_relationship() {
+    let holder = Holder::default();
+    let document: Document<Lossy<Vec<Article>>> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let article = &document.data[0];
+
+    let names = article
+        .editors
+        .iter()
+        .map(|person| person.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["John Smith", "Jane Doe"]);
+
+    assert_eq!(article.editors.errors().len(), 1);
+    assert_eq!(
+        article.editors.errors()[0].path().unwrap().to_string(),
+        "data[article:a-1].relationships.editors -> included[person:p-2].attributes.name"
+    );
+}
+
+#[test]
+fn test_wrapped_relationship() {
+    let holder = Holder::default();
+    let document: Document<Lossy<Vec<Article>>> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let reviewers = document.data[0].reviewers.data.as_ref().unwrap();
+    assert_eq!(reviewers.len(), 1);
+    assert!(reviewers.is_complete());
+
+    let reviewers = document.data[1].reviewers.data.as_ref().unwrap();
+    assert!(reviewers.is_empty());
+    assert_eq!(reviewers.errors().len(), 2);
+
+    let errors = reviewers.errors();
+    assert_eq!(
+        errors[0].path().unwrap().to_string(),
+        "data[article:a-3].relationships.reviewers -> included[person:p-2].attributes.name"
+    );
+    assert!(matches!(
+        errors[1].kind(),
+        DeserializeError::MissingResource { .. }
+    ));
+    assert_eq!(
+        errors[1].path().unwrap().to_string(),
+        "data[article:a-3].relationships.reviewers"
+    );
+}
+
+#[test]
+fn test_not_an_array() {
+    let holder = Holder::default();
+    let json = r#"{ "data": { "id": "a-1", "type": "article" } }"#;
+    let result: Result<Document<Lossy<Vec<Article>>>, Error> = deserialize_document(json, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected DeserializeError, but got {:?}", result);
+    };
+
+    assert!(matches!(error.kind(), DeserializeError::InvalidType(_)));
+}
+
+#[test]
+fn test_collect_errors() {
+    let holder = Holder::default();
+    let options = DeserializeOptions::new().collect_errors(true);
+    let json = DOCUMENT.replace(r#""attributes": {},"#, r#""attributes": { "title": 1 },"#);
+    let document: Document<Lossy<Vec<Article>>> =
+        deserialize_document_with_options(&json, &holder, &options).unwrap();
+
+    assert_eq!(document.data.len(), 2);
+    assert_eq!(document.data.errors().len(), 1);
+    assert!(matches!(
+        document.data.errors()[0].kind(),
+        DeserializeError::SerdeError(_)
+    ));
+}
+
+#[test]
+fn test_lossy_requires_multiple_relationship() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/lossy/*.rs");
+}
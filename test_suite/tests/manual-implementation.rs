@@ -0,0 +1,55 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Holder, IncludedMap, JsonApiDeserialize,
+};
+use serde_json::Value;
+
+/// Implemented by hand, the way it had to be done before the trait gained provided methods.
+#[derive(Debug, Default)]
+struct Tag {
+    label: String,
+}
+
+impl<'gc> JsonApiDeserialize<'gc> for Tag {
+    type ErasedLifetime = Tag;
+
+    fn from_value(
+        value: &Value,
+        _included_map: &mut IncludedMap<'_, 'gc>,
+    ) -> Result<Self, DeserializeError> {
+        let label = value
+            .pointer("/attributes/label")
+            .and_then(Value::as_str)
+            .ok_or(DeserializeError::MissingField("label"))?;
+
+        Ok(Self {
+            label: label.to_string(),
+        })
+    }
+
+    fn stub() -> Self {
+        Self::default()
+    }
+}
+
+#[test]
+fn test_manual_implementation() {
+    let holder = Holder::default();
+    let document: Document<Tag> = deserialize_document(
+        r#"{
+            "data": {
+                "id": "t-1",
+                "type": "tag",
+                "attributes": {
+                    "label": "News"
+                }
+            }
+        }"#,
+        &holder,
+    )
+    .unwrap();
+
+    assert_eq!(document.data.label, "News");
+    assert!(Tag::accepts_resource_type("anything"));
+}
@@ -0,0 +1,171 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize,
+    LocalReference, MaybeIncluded,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: MaybeIncluded<'a, Person>,
+    #[json_api(relationship = "optional", resource = "Person")]
+    reviewer: Option<MaybeIncluded<'a, Person>>,
+    #[json_api(relationship = "multiple", resource = "Person")]
+    contributors: Vec<MaybeIncluded<'a, Person>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "reviewer": {
+                "data": { "type": "person", "id": "p-2" }
+            },
+            "contributors": {
+                "data": [
+                    { "type": "person", "id": "p-1" },
+                    { "type": "person", "id": "p-2" }
+                ]
+            }
+        }
+    }
+}"#;
+
+#[test]
+fn test_not_included() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(!document.data.author.is_included());
+    assert_eq!(
+        document.data.author.not_included(),
+        Some(&LocalReference::new("person", "p-1"))
+    );
+    assert!(document
+        .data
+        .reviewer
+        .as_ref()
+        .unwrap()
+        .included()
+        .is_none());
+    assert_eq!(document.data.contributors.len(), 2);
+}
+
+#[test]
+fn test_partially_included() {
+    let json = DOCUMENT.replace(
+        "\n}",
+        r#",
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith"
+                }
+            }
+        ]
+    }"#,
+    );
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(&json, &holder).unwrap();
+
+    let author = document.data.author.included().unwrap();
+    assert_eq!(author.name, "John Smith");
+    assert!(!document.data.reviewer.as_ref().unwrap().is_included());
+    assert!(std::ptr::eq(
+        document.data.contributors[0].included().unwrap(),
+        author
+    ));
+    assert_eq!(
+        document.data.contributors[1]
+            .not_included()
+            .unwrap()
+            .id
+            .as_deref(),
+        Some("p-2")
+    );
+}
+
+#[test]
+fn test_included_resource_must_be_valid() {
+    let json = DOCUMENT.replace(
+        "\n}",
+        r#",
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {}
+            }
+        ]
+    }"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, _> = deserialize_document(&json, &holder);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_inconsistent_lid_is_not_hidden() {
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(
+        r#"{
+            "data": {
+                "id": "a-1",
+                "type": "article",
+                "attributes": {
+                    "title": "Foo"
+                },
+                "relationships": {
+                    "author": {
+                        "data": { "type": "person", "id": "p-2", "lid": "local-person" }
+                    },
+                    "reviewer": {
+                        "data": null
+                    },
+                    "contributors": {
+                        "data": []
+                    }
+                }
+            },
+            "included": [
+                {
+                    "type": "person",
+                    "id": "p-1",
+                    "lid": "local-person",
+                    "attributes": {
+                        "name": "John Smith"
+                    }
+                }
+            ]
+        }"#,
+        &holder,
+    );
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(
+                error.kind(),
+                DeserializeError::InconsistentLid { .. }
+            ))
+        }
+        _ => panic!("Expected InconsistentLid, but got {:?}", result),
+    }
+}
@@ -4,7 +4,7 @@ use jsonapi_deserialize::{deserialize_document, Document, JsonApiDeserialize, Re
 use zonbi::Zonbi;
 
 #[derive(Debug, JsonApiDeserialize, Zonbi, Default)]
-struct Resource<'gc>  {
+struct Resource<'gc> {
     id: String,
     #[json_api(default)]
     default_string: String,
@@ -21,7 +21,6 @@ struct Resource<'gc>  {
 
     #[json_api(optional, relationship = "optional", resource = "Resource")]
     optional_self_ref: Option<Option<&'gc Resource<'gc>>>,
-
 }
 
 #[test]
@@ -34,9 +33,9 @@ fn test_optional_missing_attribute() {
                     "type": "resource"
                 }
             }"#,
-        &holder
+        &holder,
     )
-        .unwrap();
+    .unwrap();
 
     assert_eq!(document.data.default_string.as_str(), "");
     assert_eq!(document.data.default_option, None);
@@ -61,7 +60,7 @@ fn test_optional_set_fields() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
@@ -87,7 +86,8 @@ fn test_optional_null_field() {
                     "optionalNullable": null
                 }
             }
-        }"#, &holder
+        }"#,
+        &holder,
     )
     .unwrap();
 
@@ -103,7 +103,8 @@ fn test_optional_missing_relationships() {
                 "id": "1",
                 "type": "resource"
             }
-        }"#, &holder
+        }"#,
+        &holder,
     )
     .unwrap();
 
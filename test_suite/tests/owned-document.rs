@@ -0,0 +1,182 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_owned_document, Error, Holder, JsonApiDeserialize, Lazy, OwnedDocument,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+    #[json_api(relationship = "multiple", resource = "Comment")]
+    comments: Vec<&'a Comment<'a>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment<'a> {
+    content: String,
+    #[json_api(relationship = "single", resource = "Article")]
+    article: Lazy<'a, Article<'a>>,
+}
+
+fn load_article(title: &str) -> OwnedDocument<Article<'static>> {
+    let json = format!(
+        r#"{{
+            "data": {{
+                "id": "a-1",
+                "type": "article",
+                "attributes": {{
+                    "title": "{title}"
+                }},
+                "relationships": {{
+                    "author": {{
+                        "data": {{ "type": "person", "id": "p-1" }}
+                    }},
+                    "comments": {{
+                        "data": [{{ "type": "comment", "id": "c-1" }}]
+                    }}
+                }}
+            }},
+            "meta": {{
+                "copyright": "ACME"
+            }},
+            "included": [
+                {{
+                    "type": "person",
+                    "id": "p-1",
+                    "attributes": {{
+                        "name": "John Smith"
+                    }}
+                }},
+                {{
+                    "type": "comment",
+                    "id": "c-1",
+                    "attributes": {{
+                        "content": "Lorem Ipsum"
+                    }},
+                    "relationships": {{
+                        "article": {{
+                            "data": {{ "type": "article", "id": "a-1" }}
+                        }}
+                    }}
+                }},
+                {{
+                    "type": "article",
+                    "id": "a-1",
+                    "attributes": {{
+                        "title": "{title}"
+                    }},
+                    "relationships": {{
+                        "author": {{
+                            "data": {{ "type": "person", "id": "p-1" }}
+                        }},
+                        "comments": {{
+                            "data": [{{ "type": "comment", "id": "c-1" }}]
+                        }}
+                    }}
+                }}
+            ]
+        }}"#
+    );
+
+    deserialize_owned_document(&json).unwrap()
+}
+
+struct Cache {
+    articles: HashMap<String, OwnedDocument<Article<'static>>>,
+}
+
+#[test]
+fn test_return_from_function() {
+    let document = load_article("Foo");
+
+    assert_eq!(document.data().title, "Foo");
+    assert_eq!(document.data().author.name, "John Smith");
+    assert_eq!(
+        document.data().comments[0].article.get().unwrap().title,
+        "Foo"
+    );
+    assert_eq!(
+        document.meta().unwrap().get("copyright").unwrap(),
+        &serde_json::json!("ACME")
+    );
+}
+
+#[test]
+fn test_store_and_move() {
+    let mut cache = Cache {
+        articles: HashMap::new(),
+    };
+    cache
+        .articles
+        .insert("a-1".to_string(), load_article("Foo"));
+    cache
+        .articles
+        .insert("a-2".to_string(), load_article("Bar"));
+
+    let document = cache.articles.remove("a-1").unwrap();
+    let moved = Box::new(document);
+
+    assert_eq!(moved.data().title, "Foo");
+    assert_eq!(moved.document().data.comments[0].content, "Lorem Ipsum");
+    assert_eq!(cache.articles["a-2"].data().author.name, "John Smith");
+}
+
+#[test]
+fn test_collection() {
+    let document: OwnedDocument<Vec<Person>> = deserialize_owned_document(
+        r#"{
+            "data": [
+                { "type": "person", "id": "p-1", "attributes": { "name": "John Smith" } },
+                { "type": "person", "id": "p-2", "attributes": { "name": "Jane Doe" } }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(document.data().len(), 2);
+    assert_eq!(document.data()[1].name, "Jane Doe");
+}
+
+#[test]
+fn test_custom_meta() {
+    #[derive(Debug, Deserialize)]
+    struct PageMeta {
+        total: u32,
+    }
+
+    let document: OwnedDocument<Vec<Person>, Holder, PageMeta> = deserialize_owned_document(
+        r#"{
+            "data": [],
+            "meta": {
+                "total": 42
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(document.meta().unwrap().total, 42);
+}
+
+#[test]
+fn test_document_errors() {
+    let result: Result<OwnedDocument<Person>, Error> =
+        deserialize_owned_document(r#"{ "errors": [{ "status": "500" }] }"#);
+
+    assert!(matches!(result, Err(Error::DocumentError(_))));
+}
+
+#[test]
+fn test_resources_must_be_covariant() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/owned/*.rs");
+}
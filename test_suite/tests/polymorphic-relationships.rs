@@ -0,0 +1,188 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize, Lazy,
+};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment<'a> {
+    id: String,
+    content: String,
+    #[json_api(relationship = "single", resource = "Commentable")]
+    commentable: &'a Commentable<'a>,
+    #[json_api(relationship = "optional", resource = "Commentable")]
+    pinned_to: Option<&'a Commentable<'a>>,
+    #[json_api(relationship = "multiple", resource = "Commentable")]
+    mentions: Vec<&'a Commentable<'a>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+enum Commentable<'a> {
+    Article(Article<'a>),
+    Video(Video),
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    // Points back at the comments referring to the article, which requires a lazy relationship.
+    #[json_api(relationship = "optional", resource = "Comment")]
+    featured_comment: Option<Lazy<'a, Comment<'a>>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "videos")]
+struct Video {
+    id: String,
+    url: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "c-1",
+        "type": "comment",
+        "attributes": {
+            "content": "Lorem Ipsum"
+        },
+        "relationships": {
+            "commentable": {
+                "data": { "type": "article", "id": "a-1" }
+            },
+            "pinnedTo": {
+                "data": null
+            },
+            "mentions": {
+                "data": [
+                    { "type": "videos", "id": "v-1" },
+                    { "type": "article", "id": "a-1" }
+                ]
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "article",
+            "id": "a-1",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "featuredComment": {
+                    "data": { "type": "comment", "id": "c-2" }
+                }
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-2",
+            "attributes": {
+                "content": "Dolor sit amet"
+            },
+            "relationships": {
+                "commentable": {
+                    "data": { "type": "article", "id": "a-1" }
+                },
+                "pinnedTo": {
+                    "data": { "type": "videos", "id": "v-1" }
+                },
+                "mentions": {
+                    "data": []
+                }
+            }
+        },
+        {
+            "type": "videos",
+            "id": "v-1",
+            "attributes": {
+                "url": "https://example.com/v-1.mp4"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_single_relationship_dispatches_on_type() {
+    let holder = Holder::default();
+    let document: Document<Comment> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let Commentable::Article(article) = document.data.commentable else {
+        panic!("Expected an article, got {:?}", document.data.commentable);
+    };
+    assert_eq!(article.title, "Foo");
+    assert!(document.data.pinned_to.is_none());
+}
+
+#[test]
+fn test_multiple_relationship_mixes_types() {
+    let holder = Holder::default();
+    let document: Document<Comment> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.mentions.len(), 2);
+    let Commentable::Video(video) = document.data.mentions[0] else {
+        panic!("Expected a video, got {:?}", document.data.mentions[0]);
+    };
+    assert_eq!(video.url, "https://example.com/v-1.mp4");
+    assert!(std::ptr::eq(
+        document.data.mentions[1],
+        document.data.commentable
+    ));
+}
+
+#[test]
+fn test_cycle_through_enum() {
+    let holder = Holder::default();
+    let document: Document<Comment> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    let Commentable::Article(article) = document.data.commentable else {
+        panic!("Expected an article");
+    };
+    let featured = article.featured_comment.as_ref().unwrap().get().unwrap();
+    assert_eq!(featured.content, "Dolor sit amet");
+    assert!(std::ptr::eq(
+        featured.commentable,
+        document.data.commentable
+    ));
+    assert!(matches!(featured.pinned_to, Some(Commentable::Video(_))));
+}
+
+#[test]
+fn test_enum_as_primary_data() {
+    let holder = Holder::default();
+    let document: Document<Commentable> = deserialize_document(
+        r#"{
+            "data": {
+                "id": "v-1",
+                "type": "videos",
+                "attributes": {
+                    "url": "https://example.com/v-1.mp4"
+                }
+            }
+        }"#,
+        &holder,
+    )
+    .unwrap();
+
+    assert!(matches!(document.data, Commentable::Video(video) if video.id == "v-1"));
+}
+
+#[test]
+fn test_unknown_type() {
+    let holder = Holder::default();
+    let result: Result<Document<Commentable>, Error> = deserialize_document(
+        r#"{
+            "data": {
+                "id": "p-1",
+                "type": "person"
+            }
+        }"#,
+        &holder,
+    );
+
+    match result {
+        Err(Error::DeserializeError(DeserializeError::ResourceTypeMismatch { found, .. })) => {
+            assert_eq!(found, "person")
+        }
+        _ => panic!("Expected ResourceTypeMismatch, but got {:?}", result),
+    }
+}
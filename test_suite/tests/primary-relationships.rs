@@ -0,0 +1,203 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_shared_document, DeserializeError, Document, Error, Holder,
+    JsonApiDeserialize, SharedDocument,
+};
+use std::sync::{Arc, Weak};
+
+#[derive(Debug, Default, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "multiple", resource = "Comment")]
+    comments: Vec<&'a Comment<'a>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment<'a> {
+    content: String,
+    #[json_api(relationship = "single", resource = "Article")]
+    article: &'a Article<'a>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct LinkedArticle<'a> {
+    id: String,
+    #[json_api(relationship = "optional", resource = "LinkedArticle")]
+    previous: Option<&'a LinkedArticle<'a>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct SharedArticle {
+    id: String,
+    #[json_api(relationship = "multiple", resource = "SharedComment")]
+    comments: Vec<Arc<SharedComment>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "comment")]
+struct SharedComment {
+    content: String,
+    #[json_api(relationship = "single", resource = "SharedArticle")]
+    article: Weak<SharedArticle>,
+}
+
+const SINGLE_DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "comments": {
+                "data": [
+                    { "type": "comment", "id": "c-1" },
+                    { "type": "comment", "id": "c-2" }
+                ]
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "comment",
+            "id": "c-1",
+            "attributes": {
+                "content": "Lorem Ipsum"
+            },
+            "relationships": {
+                "article": {
+                    "data": { "type": "article", "id": "a-1" }
+                }
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-2",
+            "attributes": {
+                "content": "Dolor sit amet"
+            },
+            "relationships": {
+                "article": {
+                    "data": { "type": "article", "id": "a-1" }
+                }
+            }
+        }
+    ]
+}"#;
+
+const COLLECTION_DOCUMENT: &str = r#"{
+    "data": [
+        {
+            "id": "a-1",
+            "type": "article",
+            "relationships": {
+                "previous": {
+                    "data": null
+                }
+            }
+        },
+        {
+            "id": "a-2",
+            "type": "article",
+            "relationships": {
+                "previous": {
+                    "data": { "type": "article", "id": "a-1" }
+                }
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_included_refers_to_primary_data() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(SINGLE_DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.title, "Foo");
+    assert_eq!(document.data.comments.len(), 2);
+
+    for comment in &document.data.comments {
+        assert!(std::ptr::eq(comment.article, document.data));
+    }
+}
+
+#[test]
+fn test_cycle_without_stub_is_rejected() {
+    // Without a `Default`, there is no stub to hand out while the article is deserialized.
+    #[derive(Debug, JsonApiDeserialize)]
+    #[json_api(resource_type = "article")]
+    struct EagerArticle<'a> {
+        #[json_api(relationship = "multiple", resource = "EagerComment")]
+        comments: Vec<&'a EagerComment<'a>>,
+    }
+
+    #[derive(Debug, JsonApiDeserialize)]
+    #[json_api(resource_type = "comment")]
+    struct EagerComment<'a> {
+        #[json_api(relationship = "single", resource = "EagerArticle")]
+        article: &'a EagerArticle<'a>,
+    }
+
+    let holder = Holder::default();
+    let result: Result<Document<EagerArticle>, Error> =
+        deserialize_document(SINGLE_DOCUMENT, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected CyclicReference, but got {:?}", result);
+    };
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::CyclicReference { kind, id } if kind == "article" && id == "a-1"
+    ));
+}
+
+#[test]
+fn test_primary_data_refers_to_primary_data() {
+    let holder = Holder::default();
+    let document: Document<Vec<&LinkedArticle>> =
+        deserialize_document(COLLECTION_DOCUMENT, &holder).unwrap();
+
+    assert_eq!(document.data.len(), 2);
+    assert!(document.data[0].previous.is_none());
+    assert!(std::ptr::eq(
+        document.data[1].previous.unwrap(),
+        document.data[0]
+    ));
+}
+
+#[test]
+fn test_shared_refers_to_primary_data() {
+    let document: SharedDocument<Arc<SharedArticle>> =
+        deserialize_shared_document(SINGLE_DOCUMENT).unwrap();
+
+    assert_eq!(document.data.id, "a-1");
+
+    for comment in &document.data.comments {
+        assert!(Arc::ptr_eq(
+            &comment.article.upgrade().unwrap(),
+            &document.data
+        ));
+    }
+}
+
+#[test]
+fn test_shared_owned_primary_data_is_not_shared() {
+    // Owned primary data cannot be pointed at, so the comments deserialize a second article,
+    // which in turn points back at the comments still being deserialized.
+    let result: Result<SharedDocument<SharedArticle>, Error> =
+        deserialize_shared_document(SINGLE_DOCUMENT);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected CyclicReference, but got {:?}", result);
+    };
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::CyclicReference { kind, .. } if kind == "comment"
+    ));
+}
@@ -46,7 +46,7 @@ fn test_deserialize() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
@@ -0,0 +1,156 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, Document, Holder, JsonApiDeserialize, Reference, Relationship,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct CountMeta {
+    count: u32,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    #[json_api(relationship = "single", resource = "Person", relationship_object)]
+    author: Relationship<&'a Person>,
+    #[json_api(relationship = "optional", resource = "Person", relationship_object)]
+    reviewer: Relationship<Option<&'a Person>>,
+    #[json_api(relationship = "multiple", relationship_object)]
+    comments: Relationship<Vec<Reference>, CountMeta>,
+    #[json_api(relationship = "multiple", resource = "Person", relationship_object)]
+    editors: Relationship<Vec<&'a Person>>,
+    #[json_api(relationship = "single", default, relationship_object)]
+    publisher: Relationship<Reference>,
+    #[json_api(relationship = "optional", optional, relationship_object)]
+    series: Option<Relationship<Option<Reference>>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "relationships": {
+            "author": {
+                "links": {
+                    "self": "https://example.com/articles/a-1/relationships/author",
+                    "related": "https://example.com/articles/a-1/author"
+                },
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "reviewer": {
+                "data": null
+            },
+            "comments": {
+                "links": {
+                    "related": "https://example.com/articles/a-1/comments",
+                    "next": "https://example.com/articles/a-1/comments?page[offset]=2",
+                    "prev": null
+                },
+                "meta": {
+                    "count": 42
+                }
+            },
+            "editors": {
+                "data": [
+                    { "type": "person", "id": "p-1" }
+                ],
+                "meta": {
+                    "source": "cms"
+                }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_linkage() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let article = document.data;
+
+    assert_eq!(article.author.data.unwrap().name, "John Smith");
+    assert!(article.author.meta.is_none());
+    assert!(matches!(article.reviewer.data, Some(None)));
+    assert_eq!(article.editors.data.as_ref().unwrap().len(), 1);
+    assert!(std::ptr::eq(
+        article.editors.data.as_ref().unwrap()[0],
+        article.author.data.unwrap()
+    ));
+}
+
+#[test]
+fn test_links_without_data() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let comments = &document.data.comments;
+
+    assert!(comments.data.is_none());
+    assert_eq!(comments.meta.as_ref().unwrap().count, 42);
+
+    let links = comments.links.as_ref().unwrap();
+    assert_eq!(
+        links.related.as_ref().unwrap().href,
+        "https://example.com/articles/a-1/comments"
+    );
+    assert_eq!(
+        links.next.as_ref().unwrap().href,
+        "https://example.com/articles/a-1/comments?page[offset]=2"
+    );
+    assert!(links.prev.is_none());
+    assert!(links.this.is_none());
+}
+
+#[test]
+fn test_relationship_links_and_meta() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let article = document.data;
+
+    let links = article.author.links.as_ref().unwrap();
+    assert_eq!(
+        links.this.as_ref().unwrap().href,
+        "https://example.com/articles/a-1/relationships/author"
+    );
+    assert_eq!(
+        article.editors.meta.as_ref().unwrap()["source"],
+        serde_json::json!("cms")
+    );
+}
+
+#[test]
+fn test_missing_relationship() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(document.data.publisher.data.is_none());
+    assert!(document.data.publisher.links.is_none());
+    assert!(document.data.series.is_none());
+}
+
+#[test]
+fn test_null_single_linkage_fails() {
+    let json = DOCUMENT.replace(
+        r#""data": { "type": "person", "id": "p-1" }"#,
+        r#""data": null"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, _> = deserialize_document(&json, &holder);
+
+    assert!(result.is_err());
+}
@@ -43,7 +43,7 @@ fn test_snake_case() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
@@ -63,7 +63,7 @@ fn test_pascal_case() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
@@ -83,7 +83,7 @@ fn test_camel_case() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
@@ -103,7 +103,7 @@ fn test_field_rename() {
                 }
             }
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
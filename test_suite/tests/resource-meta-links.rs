@@ -0,0 +1,153 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, DeserializeError, Document, Error, Holder, JsonApiDeserialize,
+    ResourceLinks,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Permissions {
+    can_edit: bool,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(meta)]
+    meta: Permissions,
+    #[json_api(links)]
+    links: ResourceLinks,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+    #[json_api(meta)]
+    meta: Option<Permissions>,
+    #[json_api(links)]
+    links: Option<ResourceLinks>,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        },
+        "meta": {
+            "canEdit": true
+        },
+        "links": {
+            "self": "https://example.com/articles/a-1",
+            "canonical": { "href": "https://example.com/foo" }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            },
+            "meta": {
+                "canEdit": false
+            },
+            "links": {
+                "self": "https://example.com/people/p-1"
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn test_primary_data() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+
+    assert!(document.data.meta.can_edit);
+    assert_eq!(
+        document.data.links.this.as_ref().unwrap().href,
+        "https://example.com/articles/a-1"
+    );
+    assert_eq!(
+        document.data.links.other["canonical"]
+            .as_ref()
+            .unwrap()
+            .href,
+        "https://example.com/foo"
+    );
+}
+
+#[test]
+fn test_included() {
+    let holder = Holder::default();
+    let document: Document<Article> = deserialize_document(DOCUMENT, &holder).unwrap();
+    let author = document.data.author;
+
+    assert!(!author.meta.as_ref().unwrap().can_edit);
+    assert_eq!(
+        author.links.as_ref().unwrap().this.as_ref().unwrap().href,
+        "https://example.com/people/p-1"
+    );
+}
+
+#[test]
+fn test_optional_members() {
+    let json = DOCUMENT.replace(r#""canEdit": false"#, "").replace(
+        r#""links": {
+                "self": "https://example.com/people/p-1"
+            }"#,
+        r#""links": null"#,
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    // The meta object is present, but does not match the type.
+    assert!(result.is_err());
+
+    let json = r#"{
+        "data": {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    }"#;
+    let document: Document<Person> = deserialize_document(json, &holder).unwrap();
+
+    assert!(document.data.meta.is_none());
+    assert!(document.data.links.is_none());
+}
+
+#[test]
+fn test_missing_required_member() {
+    let json = DOCUMENT.replace(
+        r#""meta": {
+            "canEdit": true
+        },"#,
+        "",
+    );
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(&json, &holder);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected MissingField, but got {:?}", result);
+    };
+
+    assert!(matches!(
+        error.kind(),
+        DeserializeError::MissingField("meta")
+    ));
+}
@@ -3,7 +3,7 @@
 use jsonapi_deserialize::{deserialize_document, Document, Holder, JsonApiDeserialize};
 use std::sync::Arc;
 
-#[derive(Debug, JsonApiDeserialize, Default)]
+#[derive(Debug, JsonApiDeserialize)]
 struct Article<'a> {
     id: String,
     title: String,
@@ -79,12 +79,12 @@ fn test_deserialize() {
                 }
             ]
         }"#,
-        &holder
+        &holder,
     )
     .unwrap();
 
     assert_eq!(document.data.title, "Foo".to_string());
-    // assert_eq!(document.data.author.name, "John Smith");
+    assert_eq!(document.data.author.name, "John Smith");
     assert_eq!(document.data.reviewer.as_ref().unwrap().name, "John Smith");
     assert!(document.data.publisher.is_none());
 
@@ -0,0 +1,312 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_shared_document, DeserializeError, Error, IncludedMap, JsonApiDeserialize,
+    LocalReference, RelationshipTarget, SharedDocument,
+};
+use serde_json::Value;
+use std::rc::{self, Rc};
+use std::sync::{Arc, Weak};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: Arc<Person>,
+    #[json_api(relationship = "optional", resource = "Person")]
+    reviewer: Option<Arc<Person>>,
+    #[json_api(relationship = "multiple", resource = "Comment")]
+    comments: Vec<Arc<Comment>>,
+    #[json_api(relationship = "single", resource = "Category")]
+    category: Arc<Category>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Comment {
+    content: String,
+    #[json_api(relationship = "optional", resource = "Person")]
+    author: Option<Arc<Person>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Category {
+    id: String,
+    #[json_api(relationship = "optional", resource = "Category")]
+    parent: Option<Arc<Category>>,
+    #[json_api(relationship = "multiple", resource = "Category")]
+    children: Vec<Weak<Category>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct StrongArticle {
+    #[json_api(relationship = "single", resource = "StrongCategory")]
+    category: Arc<StrongCategory>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "category")]
+struct StrongCategory {
+    #[json_api(relationship = "optional", resource = "StrongCategory")]
+    parent: Option<Arc<StrongCategory>>,
+    #[json_api(relationship = "multiple", resource = "StrongCategory")]
+    children: Vec<Arc<StrongCategory>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct RcArticle {
+    #[json_api(relationship = "single", resource = "Person")]
+    author: Rc<Person>,
+    #[json_api(relationship = "multiple", resource = "RcComment")]
+    comments: Vec<Rc<RcComment>>,
+    #[json_api(relationship = "single", resource = "RcCategory")]
+    category: Rc<RcCategory>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "comment")]
+struct RcComment {
+    #[json_api(relationship = "optional", resource = "Person")]
+    author: Option<Rc<Person>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "category")]
+struct RcCategory {
+    #[json_api(relationship = "optional", resource = "RcCategory")]
+    parent: Option<Rc<RcCategory>>,
+    #[json_api(relationship = "multiple", resource = "RcCategory")]
+    children: Vec<rc::Weak<RcCategory>>,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+#[json_api(resource_type = "article")]
+struct UpgradingArticle {
+    #[json_api(relationship = "single", resource = "UpgradingCategory")]
+    category: Arc<UpgradingCategory>,
+}
+
+/// Upgrades its children while it is deserialized, some of which are still being deserialized.
+#[derive(Debug)]
+struct UpgradingCategory {
+    parent: Option<Arc<UpgradingCategory>>,
+    upgraded_children: usize,
+}
+
+impl<'gc> JsonApiDeserialize<'gc> for UpgradingCategory {
+    type ErasedLifetime = UpgradingCategory;
+
+    fn from_value(
+        value: &Value,
+        included_map: &mut IncludedMap<'_, 'gc>,
+    ) -> Result<Self, DeserializeError> {
+        let relationships = &value["relationships"];
+        let parent: Option<LocalReference> =
+            serde_json::from_value(relationships["parent"]["data"].clone())?;
+        let children: Vec<LocalReference> =
+            serde_json::from_value(relationships["children"]["data"].clone())?;
+
+        let parent = match parent {
+            Some(parent) => Some(Arc::resolve(included_map, &parent)?),
+            None => None,
+        };
+        let mut upgraded_children = 0;
+
+        for child in &children {
+            let child: Weak<UpgradingCategory> = RelationshipTarget::resolve(included_map, child)?;
+
+            if child.upgrade().is_some() {
+                upgraded_children += 1;
+            }
+        }
+
+        Ok(Self {
+            parent,
+            upgraded_children,
+        })
+    }
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "reviewer": {
+                "data": { "type": "person", "id": "p-1" }
+            },
+            "comments": {
+                "data": [
+                    { "type": "comment", "id": "c-1" },
+                    { "type": "comment", "id": "c-2" }
+                ]
+            },
+            "category": {
+                "data": { "type": "category", "id": "cat-2" }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-1",
+            "attributes": {
+                "content": "Lorem Ipsum"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        {
+            "type": "comment",
+            "id": "c-2",
+            "attributes": {
+                "content": "Dolor sit amet"
+            },
+            "relationships": {
+                "author": {
+                    "data": null
+                }
+            }
+        },
+        {
+            "type": "category",
+            "id": "cat-1",
+            "relationships": {
+                "parent": {
+                    "data": null
+                },
+                "children": {
+                    "data": [
+                        { "type": "category", "id": "cat-2" }
+                    ]
+                }
+            }
+        },
+        {
+            "type": "category",
+            "id": "cat-2",
+            "relationships": {
+                "parent": {
+                    "data": { "type": "category", "id": "cat-1" }
+                },
+                "children": {
+                    "data": []
+                }
+            }
+        }
+    ]
+}"#;
+
+fn load() -> SharedDocument<Article> {
+    deserialize_shared_document(DOCUMENT).unwrap()
+}
+
+#[test]
+fn test_arc_relationships() {
+    let document = load();
+
+    assert_eq!(document.data.title, "Foo");
+    assert_eq!(document.data.author.name, "John Smith");
+    assert!(Arc::ptr_eq(
+        &document.data.author,
+        document.data.reviewer.as_ref().unwrap()
+    ));
+    assert_eq!(document.data.comments.len(), 2);
+    assert!(Arc::ptr_eq(
+        &document.data.author,
+        document.data.comments[0].author.as_ref().unwrap()
+    ));
+    assert!(document.data.comments[1].author.is_none());
+}
+
+#[test]
+fn test_weak_back_edges() {
+    let document = load();
+    let category = &document.data.category;
+    let parent = category.parent.as_ref().unwrap();
+
+    assert_eq!(category.id, "cat-2");
+    assert_eq!(parent.id, "cat-1");
+    assert!(parent.parent.is_none());
+    assert_eq!(parent.children.len(), 1);
+    assert!(Arc::ptr_eq(
+        &parent.children[0].upgrade().unwrap(),
+        category
+    ));
+}
+
+#[test]
+fn test_document_is_static() {
+    fn assert_static<T: 'static + Send + Sync>(_: &T) {}
+
+    let document = load();
+    assert_static(&document);
+
+    let author = std::thread::spawn(move || document.data.author.clone())
+        .join()
+        .unwrap();
+    assert_eq!(author.name, "John Smith");
+}
+
+#[test]
+fn test_rc_relationships() {
+    let document: SharedDocument<RcArticle> = deserialize_shared_document(DOCUMENT).unwrap();
+
+    assert!(Rc::ptr_eq(
+        &document.data.author,
+        document.data.comments[0].author.as_ref().unwrap()
+    ));
+
+    let category = &document.data.category;
+    let parent = category.parent.as_ref().unwrap();
+    assert!(Rc::ptr_eq(&parent.children[0].upgrade().unwrap(), category));
+}
+
+#[test]
+fn test_strong_cycle_fails() {
+    let result: Result<SharedDocument<StrongArticle>, Error> =
+        deserialize_shared_document(DOCUMENT);
+
+    let Err(Error::DeserializeError(error)) = result else {
+        panic!("Expected CyclicReference, but got {:?}", result);
+    };
+
+    match error.kind() {
+        DeserializeError::CyclicReference { kind, id } => {
+            assert_eq!(kind, "category");
+            assert_eq!(id, "cat-2");
+        }
+        _ => panic!("Expected CyclicReference, but got {:?}", error),
+    }
+}
+
+#[test]
+fn test_pending_resource_cannot_be_upgraded() {
+    let document: SharedDocument<UpgradingArticle> = deserialize_shared_document(DOCUMENT).unwrap();
+
+    let parent = document.data.category.parent.as_ref().unwrap();
+    assert_eq!(parent.upgraded_children, 0);
+}
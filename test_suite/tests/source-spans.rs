@@ -0,0 +1,269 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_error_document, DeserializeError, Document, Error, Holder,
+    JsonApiDeserialize, Span,
+};
+use miette::Diagnostic;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[test]
+fn test_invalid_attribute() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith",
+                    "age": "forty-two"
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let span = error.span().unwrap();
+
+            assert_eq!(&json[span.offset..span.offset + span.len], r#""forty-two""#);
+            assert_eq!(span.line, 20);
+            assert_eq!(span.column, 28);
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_missing_attribute() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith",
+                    "age": 42
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let span = error.span().unwrap();
+
+            // Points at the object the attribute is missing from.
+            assert!(json[span.offset..].starts_with('{'));
+            assert_eq!(span.line, 5);
+            assert_eq!(span.column, 27);
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_duplicate_included_resource() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith",
+                    "age": "unknown"
+                }
+            },
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith",
+                    "age": "forty-two"
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let span = error.span().unwrap();
+
+            // The last of the duplicates is the one which is deserialized.
+            assert_eq!(&json[span.offset..span.offset + span.len], r#""forty-two""#);
+            assert_eq!(span.line, 28);
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_syntax_error() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article"
+            "attributes": {
+                "title": "Foo"
+            }
+        }
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    // Errors without a path are not wrapped to carry a span.
+    match result {
+        Err(Error::DeserializeError(DeserializeError::SerdeError(error))) => {
+            assert_eq!(error.line(), 5);
+        }
+        _ => panic!("Expected SerdeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_error_document() {
+    let error = deserialize_error_document(r#"{ "errors": 42 }"#).unwrap_err();
+
+    match error {
+        DeserializeError::SerdeError(error) => assert_eq!(error.line(), 1),
+        _ => panic!("Expected SerdeError, but got {:?}", error),
+    }
+}
+
+#[test]
+fn test_no_span_without_location() {
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> =
+        deserialize_document(r#"{ "meta": {} }"#, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            assert!(matches!(error, DeserializeError::IncompleteDocument));
+            assert!(error.span().is_none());
+        }
+        _ => panic!("Expected IncompleteDocument, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_syntax_error_diagnostic() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article"
+            "attributes": {}
+        }
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let diagnostic = error.into_diagnostic(json.to_string());
+
+            let labels = diagnostic.labels().unwrap().collect::<Vec<_>>();
+            assert_eq!(labels.len(), 1);
+            assert!(json[labels[0].offset()..].starts_with(r#""attributes""#));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
+
+#[test]
+fn test_diagnostic() {
+    let json = r#"{
+        "data": {
+            "id": "a-1",
+            "type": "article",
+            "attributes": {
+                "title": "Foo"
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "person", "id": "p-1" }
+                }
+            }
+        },
+        "included": [
+            {
+                "type": "person",
+                "id": "p-1",
+                "attributes": {
+                    "name": "John Smith",
+                    "age": "forty-two"
+                }
+            }
+        ]
+    }"#;
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(json, &holder);
+
+    match result {
+        Err(Error::DeserializeError(error)) => {
+            let diagnostic = error.into_diagnostic(json.to_string());
+
+            let labels = diagnostic.labels().unwrap().collect::<Vec<_>>();
+            assert_eq!(labels.len(), 1);
+            assert_eq!(labels[0].offset(), json.find(r#""forty-two""#).unwrap());
+            assert!(diagnostic.source_code().is_some());
+            assert!(diagnostic.to_string().starts_with(
+                "data[article:a-1].relationships.author -> included[person:p-1].attributes.age"
+            ));
+        }
+        _ => panic!("Expected DeserializeError, but got {:?}", result),
+    }
+}
@@ -0,0 +1,88 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_sync_document, JsonApiDeserialize, OwnedDocument, SyncHolder,
+};
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    id: String,
+    title: String,
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "data": {
+        "id": "a-1",
+        "type": "article",
+        "attributes": {
+            "title": "Foo"
+        },
+        "relationships": {
+            "author": {
+                "data": { "type": "person", "id": "p-1" }
+            }
+        }
+    },
+    "included": [
+        {
+            "type": "person",
+            "id": "p-1",
+            "attributes": {
+                "name": "John Smith"
+            }
+        }
+    ]
+}"#;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_sync_document_is_send_and_sync() {
+    assert_send_sync::<OwnedDocument<Article<'static>, SyncHolder>>();
+}
+
+#[test]
+fn test_send_to_other_thread() {
+    let document: OwnedDocument<Article<'static>, SyncHolder> =
+        thread::spawn(|| deserialize_sync_document(DOCUMENT).unwrap())
+            .join()
+            .unwrap();
+
+    let name = thread::spawn(move || document.data().author.name.clone())
+        .join()
+        .unwrap();
+
+    assert_eq!(name, "John Smith");
+}
+
+#[test]
+fn test_share_between_threads() {
+    let document: Arc<OwnedDocument<Article<'static>, SyncHolder>> =
+        Arc::new(deserialize_sync_document(DOCUMENT).unwrap());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let document = document.clone();
+            thread::spawn(move || document.data().title.clone())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "Foo");
+    }
+}
+
+#[test]
+fn test_non_thread_safe_resources_are_rejected() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
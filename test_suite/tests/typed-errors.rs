@@ -0,0 +1,126 @@
+#![allow(unused)]
+
+use jsonapi_deserialize::{
+    deserialize_document, deserialize_error_document, Document, DocumentError, Error, Holder,
+    JsonApiDeserialize, JsonApiError,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RetryMeta {
+    retry_after: u32,
+}
+
+#[derive(Debug, JsonApiError)]
+enum ArticleError {
+    #[json_api(code = "invalid-title", status = "422")]
+    InvalidTitle {
+        #[json_api(detail)]
+        detail: Option<String>,
+        #[json_api(pointer)]
+        pointer: String,
+    },
+    #[json_api(code = "rate-limited")]
+    RateLimited(#[json_api(meta)] RetryMeta),
+    #[json_api(code = "not-found")]
+    NotFound,
+    Unknown(Box<DocumentError>),
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article {
+    id: String,
+}
+
+const DOCUMENT: &str = r#"{
+    "errors": [
+        {
+            "status": "422",
+            "code": "invalid-title",
+            "detail": "Title must not be empty",
+            "source": { "pointer": "/data/attributes/title" }
+        },
+        {
+            "status": "429",
+            "code": "rate-limited",
+            "meta": { "retryAfter": 30 }
+        },
+        {
+            "status": "404",
+            "code": "not-found"
+        },
+        {
+            "status": "500",
+            "code": "internal"
+        }
+    ]
+}"#;
+
+#[test]
+fn test_map_errors() {
+    let errors = deserialize_error_document(DOCUMENT)
+        .unwrap()
+        .into_typed::<ArticleError>();
+
+    assert_eq!(errors.len(), 4);
+
+    match &errors[0] {
+        ArticleError::InvalidTitle { detail, pointer } => {
+            assert_eq!(detail.as_deref(), Some("Title must not be empty"));
+            assert_eq!(pointer, "/data/attributes/title");
+        }
+        error => panic!("Expected InvalidTitle, but got {:?}", error),
+    }
+
+    match &errors[1] {
+        ArticleError::RateLimited(meta) => assert_eq!(meta.retry_after, 30),
+        error => panic!("Expected RateLimited, but got {:?}", error),
+    }
+
+    assert!(matches!(errors[2], ArticleError::NotFound));
+
+    match &errors[3] {
+        ArticleError::Unknown(error) => assert_eq!(error.code.as_deref(), Some("internal")),
+        error => panic!("Expected Unknown, but got {:?}", error),
+    }
+}
+
+#[test]
+fn test_mismatching_status_falls_back() {
+    let json = DOCUMENT.replace(r#""status": "422""#, r#""status": "400""#);
+    let errors = deserialize_error_document(&json)
+        .unwrap()
+        .into_typed::<ArticleError>();
+
+    assert!(matches!(errors[0], ArticleError::Unknown(_)));
+}
+
+#[test]
+fn test_missing_capture_falls_back() {
+    let json = DOCUMENT
+        .replace(
+            r#""source": { "pointer": "/data/attributes/title" }"#,
+            r#""source": {}"#,
+        )
+        .replace(r#""retryAfter": 30"#, r#""retryAfter": "soon""#);
+    let errors = deserialize_error_document(&json)
+        .unwrap()
+        .into_typed::<ArticleError>();
+
+    assert!(matches!(errors[0], ArticleError::Unknown(_)));
+    assert!(matches!(errors[1], ArticleError::Unknown(_)));
+}
+
+#[test]
+fn test_from_document_error() {
+    let holder = Holder::default();
+    let result: Result<Document<Article>, Error> = deserialize_document(DOCUMENT, &holder);
+
+    let errors = match result {
+        Err(Error::DocumentError(document)) => ArticleError::from_document_errors(document.errors),
+        _ => panic!("Expected DocumentError, but got {:?}", result),
+    };
+
+    assert!(matches!(errors[2], ArticleError::NotFound));
+}
@@ -0,0 +1,14 @@
+use jsonapi_deserialize::JsonApiDeserialize;
+
+#[derive(JsonApiDeserialize)]
+struct Article {
+    title: String,
+}
+
+#[derive(JsonApiDeserialize)]
+#[json_api(deny_unknown_fields)]
+enum Content {
+    Article(Article),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jsonapi_deserialize::JsonApiDeserialize;
+
+#[derive(JsonApiDeserialize)]
+struct Article {
+    #[json_api(ext = "version:-id")]
+    version: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jsonapi_deserialize::{JsonApiDeserialize, Reference};
+
+#[derive(JsonApiDeserialize)]
+struct Article {
+    #[json_api(relationship = "single", lossy)]
+    author: Reference,
+}
+
+fn main() {}
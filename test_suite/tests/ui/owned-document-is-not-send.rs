@@ -0,0 +1,14 @@
+use jsonapi_deserialize::{deserialize_owned_document, JsonApiDeserialize, OwnedDocument};
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    name: String,
+}
+
+fn main() {
+    let document: OwnedDocument<Person> = deserialize_owned_document(r#"{}"#).unwrap();
+
+    std::thread::spawn(move || {
+        println!("{}", document.data().name);
+    });
+}
@@ -0,0 +1,11 @@
+use jsonapi_deserialize::JsonApiDeserialize;
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+#[derive(JsonApiDeserialize)]
+struct Article<'a> {
+    #[json_api(default)]
+    invariant: PhantomData<Cell<&'a ()>>,
+}
+
+fn main() {}
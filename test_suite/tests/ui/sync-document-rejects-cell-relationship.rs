@@ -0,0 +1,17 @@
+use jsonapi_deserialize::{deserialize_sync_document, JsonApiDeserialize, OwnedDocument, SyncHolder};
+use std::cell::Cell;
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Article<'a> {
+    #[json_api(relationship = "single", resource = "Person")]
+    author: &'a Person,
+}
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    age: Cell<u32>,
+}
+
+fn main() {
+    let _: Result<OwnedDocument<Article<'static>, SyncHolder>, _> = deserialize_sync_document(r#"{}"#);
+}
@@ -0,0 +1,16 @@
+use jsonapi_deserialize::{deserialize_sync_document, JsonApiDeserialize, OwnedDocument, SyncHolder};
+use serde::Deserialize;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+#[derive(Debug, Default, Deserialize)]
+struct LocalHandle(#[serde(skip)] PhantomData<Rc<()>>);
+
+#[derive(Debug, JsonApiDeserialize)]
+struct Person {
+    handle: LocalHandle,
+}
+
+fn main() {
+    let _: Result<OwnedDocument<Person, SyncHolder>, _> = deserialize_sync_document(r#"{}"#);
+}